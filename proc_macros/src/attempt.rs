@@ -0,0 +1,149 @@
+use alloc::vec::Vec;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::visit_mut::VisitMut;
+use syn::{Block, Expr, ExprAsync, ExprClosure, ExprConst, ExprMacro, Item, Lifetime, Macro, Stmt};
+
+use crate::helper_fn::visit_macro_args;
+
+/// The body of an `attempt!` block, with bare `good!`/`bad!` marked to break out of it
+pub struct Attempt {
+    krate: TokenTree,
+    stmts: Vec<Stmt>,
+    /// Whether any `good!`/`bad!` breaks out of the block, an unused label would warn otherwise
+    breaks: bool,
+}
+
+impl Parse for Attempt {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // `$crate` from the calling `macro_rules!`
+        let krate: TokenTree = input.parse()?;
+        let mut stmts = input.call(Block::parse_within)?;
+        let mut rewriter = Rewriter {
+            label: label(),
+            breaks: false,
+        };
+        for stmt in &mut stmts {
+            rewriter.visit_stmt_mut(stmt);
+        }
+        let breaks = rewriter.breaks;
+        Ok(Attempt {
+            krate,
+            stmts,
+            breaks,
+        })
+    }
+}
+
+impl ToTokens for Attempt {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Attempt {
+            krate,
+            stmts,
+            breaks,
+        } = self;
+        let mut stmts = stmts.clone();
+        // A trailing macro without a semicolon is the tail expression of the block
+        if let Some(Stmt::Macro(stmt)) = stmts.last() {
+            if stmt.semi_token.is_none() {
+                let Some(Stmt::Macro(stmt)) = stmts.pop() else {
+                    unreachable!()
+                };
+                stmts.push(Stmt::Expr(
+                    Expr::Macro(ExprMacro {
+                        attrs: stmt.attrs,
+                        mac: stmt.mac,
+                    }),
+                    None,
+                ));
+            }
+        }
+        let tail = match stmts.last() {
+            Some(Stmt::Expr(_, None)) => {
+                let Some(Stmt::Expr(tail, None)) = stmts.pop() else {
+                    unreachable!()
+                };
+                tail.into_token_stream()
+            }
+            _ => quote! { () },
+        };
+        let body = quote! {
+            #(#stmts)*
+            #krate::FromGood::from_good(#tail)
+        };
+        tokens.extend(match breaks {
+            true => {
+                let label = label();
+                quote! { #label: { #body } }
+            }
+            false => quote! {{ #body }},
+        });
+    }
+}
+
+fn label() -> Lifetime {
+    Lifetime::new("'__attempt", Span::mixed_site())
+}
+
+struct Rewriter {
+    label: Lifetime,
+    breaks: bool,
+}
+
+impl Rewriter {
+    /// Marks a bare `good!(x)` or `bad!(x)` to break out of the block, clauses keep their usual
+    /// meaning. The macro path is kept as written, so the import stays in use.
+    fn rewrite(&mut self, mac: &mut Macro) -> bool {
+        if !is_named(mac, "good") && !is_named(mac, "bad") {
+            return false;
+        }
+        let Ok(mut scrutinee) = mac.parse_body_with(bare_expr) else {
+            return false;
+        };
+        self.visit_expr_mut(&mut scrutinee);
+        self.breaks = true;
+        let label = &self.label;
+        mac.tokens = quote! { @attempt #label #scrutinee };
+        true
+    }
+}
+
+/// A lone expression, without any clause
+fn bare_expr(input: ParseStream) -> syn::Result<Expr> {
+    let expr: Expr = input.parse()?;
+    if !input.is_empty() {
+        return Err(input.error("expected a bare expression"));
+    }
+    Ok(expr)
+}
+
+fn is_named(mac: &Macro, name: &str) -> bool {
+    mac.path
+        .segments
+        .last()
+        .is_some_and(|seg| seg.ident == name)
+}
+
+impl VisitMut for Rewriter {
+    fn visit_macro_mut(&mut self, mac: &mut Macro) {
+        if self.rewrite(mac) {
+            return;
+        }
+        // Nested `attempt!` blocks rewrite their own body, and the clauses of `good!`/`bad!`
+        // may hold closures, which can't break out of the block
+        if ["attempt", "good", "bad"]
+            .iter()
+            .any(|name| is_named(mac, name))
+        {
+            return;
+        }
+        visit_macro_args(self, mac);
+    }
+
+    // A `break` can't leave closures, async blocks, const blocks or nested items
+    fn visit_expr_closure_mut(&mut self, _: &mut ExprClosure) {}
+    fn visit_expr_async_mut(&mut self, _: &mut ExprAsync) {}
+    fn visit_expr_const_mut(&mut self, _: &mut ExprConst) {}
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+}
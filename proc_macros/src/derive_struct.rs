@@ -0,0 +1,119 @@
+use alloc::format;
+use alloc::vec::Vec;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{Error, Fields, GenericParam, Generics, Ident, LifetimeParam, Member, Type};
+
+use crate::helper_fn::{fresh_lifetime, get_result_type, StructOptions};
+
+/// `Good`, `Bad` and the index traits of a struct, classified by a predicate on its data.
+/// The struct is good when `good_if` holds and bad otherwise, so it is always two-state.
+pub fn derive_struct(
+    trait_path: &TokenStream2,
+    ident: &Ident,
+    generics: &Generics,
+    fields: &Fields,
+    options: StructOptions,
+) -> Result<TokenStream2, Error> {
+    let StructOptions { good_if, good, bad } = options;
+    let good = match good {
+        Some(member) => Some((field_type(ident, fields, &member)?, member)),
+        None => None,
+    };
+    let bad = match bad {
+        Some(member) => Some((field_type(ident, fields, &member)?, member)),
+        None => None,
+    };
+
+    // The lifetime of the `&` and `&mut` impls must not shadow a lifetime of the struct
+    let lifetime = fresh_lifetime(generics);
+    let mut generics_ref = generics.clone();
+    generics_ref.params.insert(
+        0,
+        GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (impl_generics_ref, _, _) = generics_ref.split_for_impl();
+
+    let (ok, err) = (
+        quote! {::core::result::Result::Ok},
+        quote! {::core::result::Result::Err},
+    );
+    let get_index = quote! { #trait_path __private::__GetIndex::get_index };
+    // The borrow of the payload type, of the payload, and `&Self` for the predicate
+    let borrows = [
+        (quote! {}, quote! {}, quote! {&self}),
+        (quote! {& #lifetime}, quote! {&}, quote! {self}),
+        (quote! {& #lifetime mut}, quote! {&mut}, quote! {&*self}),
+    ];
+    let mut impls = Vec::new();
+    for (is_good, projection) in [(true, &good), (false, &bad)] {
+        let (trait_name, method, index, keep, dump) = match is_good {
+            true => (quote! {Good}, quote! {good}, 0usize, &ok, &err),
+            false => (quote! {Bad}, quote! {bad}, 1usize, &err, &ok),
+        };
+        for (borrow, borrow_payload, by_ref) in &borrows {
+            let (payload_type, payload) = match projection {
+                Some((ty, member)) => (quote! {#borrow #ty}, quote! {#borrow_payload self.#member}),
+                None => (quote! {#borrow #ident #ty_generics}, quote! {self}),
+            };
+            let result_type = get_result_type(&payload_type, is_good);
+            let impl_generics = match borrow.is_empty() {
+                true => impl_generics.to_token_stream(),
+                false => impl_generics_ref.to_token_stream(),
+            };
+            impls.push(quote! {
+                impl #impl_generics #trait_path #trait_name <#payload_type> for #borrow #ident #ty_generics #where_clause {
+                    fn #method(self) -> #result_type {
+                        if #get_index(#by_ref) == #index {
+                            #keep(#payload)
+                        } else {
+                            #dump(self)
+                        }
+                    }
+                }
+            });
+        }
+    }
+
+    Ok(quote! {
+        #(#impls)*
+        impl #impl_generics #trait_path __private::__GetIndex for #ident #ty_generics #where_clause {
+            fn get_index(&self) -> usize {
+                if (#good_if)(self) { 0 } else { 1 }
+            }
+        }
+        impl #impl_generics #trait_path __private::__GoodIndex for #ident #ty_generics #where_clause {
+            fn good_indexes(&self) -> &'static [u8] {
+                &[0b01]
+            }
+        }
+        impl #impl_generics #trait_path __private::__BadIndex for #ident #ty_generics #where_clause {
+            fn bad_indexes(&self) -> &'static [u8] {
+                &[0b10]
+            }
+        }
+        impl #impl_generics #trait_path __private::__NeutralIndex for #ident #ty_generics #where_clause {
+            fn neutral_indexes(&self) -> &'static [u8] {
+                &[0b00]
+            }
+        }
+        unsafe impl #impl_generics #trait_path ExactlyTwoDistinctVariants for #ident #ty_generics #where_clause {}
+    })
+}
+
+fn field_type<'a>(ident: &Ident, fields: &'a Fields, member: &Member) -> Result<&'a Type, Error> {
+    let field = match member {
+        Member::Named(name) => fields
+            .iter()
+            .find(|field| field.ident.as_ref() == Some(name)),
+        Member::Unnamed(index) => match fields {
+            Fields::Unnamed(fields) => fields.unnamed.iter().nth(index.index as usize),
+            _ => None,
+        },
+    };
+    let msg = format!("no field `{}` on `{ident}`", member.to_token_stream());
+    field
+        .map(|field| &field.ty)
+        .ok_or_else(|| Error::new_spanned(member, msg))
+}
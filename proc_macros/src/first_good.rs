@@ -0,0 +1,79 @@
+use alloc::format;
+use alloc::vec::Vec;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Expr, Ident, Token};
+
+use crate::propagation::Propagation;
+
+/// `first_good!(<expr>, <expr>, ...; <clause>)`
+pub struct FirstGood {
+    propagation: Propagation,
+}
+
+impl Parse for FirstGood {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // `$crate` from the calling `macro_rules!`
+        let krate: TokenTree = input.parse()?;
+        let mut alternatives: Vec<Expr> = Vec::new();
+        loop {
+            if input.is_empty() || input.peek(Token![;]) || input.peek(Token![=>]) {
+                break;
+            }
+            alternatives.push(input.parse()?);
+            if !input.peek(Token![,]) {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        if alternatives.is_empty() {
+            return Err(input.error("expected at least one alternative"));
+        }
+        if input.peek(Token![=>]) {
+            let fork = input.fork();
+            fork.parse::<Token![=>]>()?;
+            if let Some(ident) = fork.parse::<Ident>().ok().filter(|ident| ident == "full") {
+                return Err(Error::new(
+                    ident.span(),
+                    "`full` is not supported by `first_good!`, the closure gets the bad value of \
+                    each two-state alternative, and the whole value of the others",
+                ));
+            }
+        }
+
+        // Each alternative is only evaluated if all before it are bad. The bad payload of a
+        // two-state alternative is kept, and the whole value of any other.
+        let bad_values: Vec<Ident> = (0..alternatives.len())
+            .map(|i| Ident::new(&format!("__bad{i}"), Span::mixed_site()))
+            .collect();
+        let value = Ident::new("v", Span::mixed_site());
+        let mut scrutinee = quote! { ::core::result::Result::Err((#(#bad_values,)*)) };
+        for (alternative, bad_value) in alternatives.iter().zip(&bad_values).rev() {
+            scrutinee = quote! {
+                match #krate::Good::good(#alternative) {
+                    ::core::result::Result::Ok(#value) => ::core::result::Result::Ok(#value),
+                    ::core::result::Result::Err(#bad_value) => {
+                        let #bad_value = (&#krate::__private::KindOf::of(&#bad_value))
+                            .__dump_kind()
+                            .bad_value(#bad_value);
+                        #scrutinee
+                    }
+                }
+            };
+        }
+        let scrutinee = Expr::Verbatim(quote! {{
+            #[allow(unused_imports)]
+            use #krate::__private::{DumpTwoStates as _, DumpWhole as _};
+            #scrutinee
+        }});
+        let propagation = Propagation::parse_take(krate, scrutinee, input)?;
+        Ok(FirstGood { propagation })
+    }
+}
+
+impl ToTokens for FirstGood {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.propagation.to_tokens(tokens);
+    }
+}
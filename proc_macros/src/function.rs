@@ -0,0 +1,65 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::visit_mut::{self, VisitMut};
+use syn::{
+    Block, Expr, ExprAsync, ExprClosure, ExprConst, ExprMacro, ExprTryBlock, Item, ItemFn, Stmt,
+};
+
+use crate::helper_fn::visit_macro_args;
+
+/// Rewrites `expr?` in the body of a function, and wraps its tail through `FromGood`
+pub fn rewrite_function(mut function: ItemFn, trait_path: &TokenStream2) -> TokenStream2 {
+    let mut rewriter = Rewriter { trait_path };
+    rewriter.visit_block_mut(&mut function.block);
+    wrap_tail(&mut function.block, trait_path);
+    function.into_token_stream()
+}
+
+fn wrap_tail(block: &mut Block, trait_path: &TokenStream2) {
+    let tail = match block.stmts.pop() {
+        Some(Stmt::Expr(tail, None)) => tail.into_token_stream(),
+        // A trailing macro without a semicolon is the tail expression of the block
+        Some(Stmt::Macro(stmt)) if stmt.semi_token.is_none() => Expr::Macro(ExprMacro {
+            attrs: stmt.attrs,
+            mac: stmt.mac,
+        })
+        .into_token_stream(),
+        // Without a tail expression, the body is left as is
+        other => {
+            block.stmts.extend(other);
+            return;
+        }
+    };
+    block.stmts.push(Stmt::Expr(
+        Expr::Verbatim(quote! { #trait_path FromGood::from_good(#tail) }),
+        None,
+    ));
+}
+
+struct Rewriter<'a> {
+    trait_path: &'a TokenStream2,
+}
+
+impl VisitMut for Rewriter<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        visit_mut::visit_expr_mut(self, expr);
+        if let Expr::Try(try_expr) = expr {
+            let trait_path = self.trait_path;
+            let inner = &try_expr.expr;
+            *expr = Expr::Verbatim(quote! {
+                #trait_path good!(#inner => #trait_path Bad)
+            });
+        }
+    }
+
+    fn visit_macro_mut(&mut self, mac: &mut syn::Macro) {
+        visit_macro_args(self, mac);
+    }
+
+    // `?` in closures, async blocks, `try` blocks and nested items keeps its usual meaning
+    fn visit_expr_closure_mut(&mut self, _: &mut ExprClosure) {}
+    fn visit_expr_async_mut(&mut self, _: &mut ExprAsync) {}
+    fn visit_expr_try_block_mut(&mut self, _: &mut ExprTryBlock) {}
+    fn visit_expr_const_mut(&mut self, _: &mut ExprConst) {}
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+}
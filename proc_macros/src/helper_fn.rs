@@ -1,12 +1,22 @@
+use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use hashbrown::{HashMap, HashSet};
-use proc_macro2::{Ident, Literal, Span, TokenStream, TokenStream as TokenStream2};
-use quote::quote;
-use syn::{Field, Fields, Type, Variant};
+use proc_macro2::{
+    Delimiter, Ident, Literal, Span, TokenStream, TokenStream as TokenStream2, TokenTree,
+};
+use quote::{quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::visit_mut::VisitMut;
+use syn::{
+    Attribute, Error, Expr, Field, Fields, GenericArgument, Generics, Lifetime, Macro, Member,
+    Meta, PathArguments, Token, Type, TypeTuple, Variant,
+};
 
-const GOOD_ATTR_NAME: &str = "good";
-const BAD_ATTR_NAME: &str = "bad";
+pub const GOOD_ATTR_NAME: &str = "good";
+pub const BAD_ATTR_NAME: &str = "bad";
+pub const NEUTRAL_ATTR_NAME: &str = "neutral";
+pub const PROPAGATE_ATTR_NAME: &str = "propagate";
 
 pub fn has_attribute(variant: &Variant, ident: &str) -> bool {
     variant.attrs.iter().any(|attr| attr.path().is_ident(ident))
@@ -20,6 +30,263 @@ pub fn has_bad_attribute(variant: &Variant) -> bool {
     has_attribute(variant, BAD_ATTR_NAME)
 }
 
+/// Options of `#[good(...)]` and `#[bad(...)]`
+#[derive(Default)]
+pub struct VariantOptions {
+    /// `#[bad(retryable)]`
+    pub retryable: bool,
+    /// `#[good(exit_code = <u8>)]` or `#[bad(exit_code = <u8>)]`
+    pub exit_code: Option<Expr>,
+}
+
+/// Parses the options of `#[good(...)]` and `#[bad(...)]`, rejecting unknown ones
+pub fn get_variant_options(variant: &Variant) -> Result<VariantOptions, Error> {
+    let mut options = VariantOptions::default();
+    for attr in &variant.attrs {
+        let is_bad = attr.path().is_ident(BAD_ATTR_NAME);
+        if !is_bad && !attr.path().is_ident(GOOD_ATTR_NAME) || matches!(attr.meta, Meta::Path(_)) {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if is_bad && meta.path.is_ident("retryable") {
+                options.retryable = true;
+                return Ok(());
+            }
+            if meta.path.is_ident("exit_code") {
+                if options.exit_code.is_some() {
+                    return Err(meta.error("`exit_code` is specified more than once"));
+                }
+                options.exit_code = Some(meta.value()?.parse()?);
+                return Ok(());
+            }
+            Err(meta.error(match is_bad {
+                true => "unknown `#[bad]` option, expected `retryable` or `exit_code`",
+                false => "unknown `#[good]` option, expected `exit_code`",
+            }))
+        })?;
+    }
+    Ok(options)
+}
+
+/// How a `Termination` impl reports bad variants to stderr
+#[derive(Clone, Copy)]
+pub enum Report {
+    Debug,
+    Display,
+}
+
+/// The impls emitted by the derive, all of them unless `#[propagate(only(...))]` picks some
+#[derive(Clone, Copy)]
+pub struct Parts {
+    pub good: bool,
+    pub bad: bool,
+    /// `Good`/`Bad` for `&Enum`
+    pub borrow: bool,
+    /// `Good`/`Bad` for `&mut Enum`
+    pub borrow_mut: bool,
+    /// `GetIndex`, `GoodIndex`, `BadIndex` and `NeutralIndex`
+    pub index: bool,
+    pub from_good: bool,
+    pub from_bad: bool,
+    /// `ExactlyTwoDistinctVariants`
+    pub two_states: bool,
+}
+
+impl Default for Parts {
+    fn default() -> Self {
+        Parts {
+            good: true,
+            bad: true,
+            borrow: true,
+            borrow_mut: true,
+            index: true,
+            from_good: true,
+            from_bad: true,
+            two_states: true,
+        }
+    }
+}
+
+/// Options of `#[propagate(...)]` on the enum
+#[derive(Default)]
+pub struct EnumOptions {
+    /// `exhaustive`
+    pub exhaustive: bool,
+    /// `termination`, `termination(debug)` or `termination(display)`
+    pub termination: Option<Report>,
+    /// `only(good, bad, ...)`
+    pub parts: Parts,
+}
+
+/// Parses the options of `#[propagate(...)]` on the enum, rejecting unknown ones
+pub fn get_enum_options(attrs: &[Attribute]) -> Result<EnumOptions, Error> {
+    let mut options = EnumOptions::default();
+    let mut only = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident(PROPAGATE_ATTR_NAME)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("exhaustive") {
+                options.exhaustive = true;
+                return Ok(());
+            }
+            if meta.path.is_ident("termination") {
+                let mut report = Report::Debug;
+                if meta.input.peek(syn::token::Paren) {
+                    meta.parse_nested_meta(|meta| {
+                        report = match meta.path.get_ident().map(ToString::to_string).as_deref() {
+                            Some("debug") => Report::Debug,
+                            Some("display") => Report::Display,
+                            _ => return Err(meta.error("expected `debug` or `display`")),
+                        };
+                        Ok(())
+                    })?;
+                }
+                options.termination = Some(report);
+                return Ok(());
+            }
+            if meta.path.is_ident("only") {
+                if only.is_some() {
+                    return Err(meta.error("`only` is specified more than once"));
+                }
+                let mut parts = Parts {
+                    good: false,
+                    bad: false,
+                    borrow: false,
+                    borrow_mut: false,
+                    index: false,
+                    from_good: false,
+                    from_bad: false,
+                    two_states: false,
+                };
+                meta.parse_nested_meta(|meta| {
+                    let part = match meta.path.get_ident().map(ToString::to_string).as_deref() {
+                        Some("good") => &mut parts.good,
+                        Some("bad") => &mut parts.bad,
+                        Some("borrow") => &mut parts.borrow,
+                        Some("borrow_mut") => &mut parts.borrow_mut,
+                        Some("index") => &mut parts.index,
+                        Some("from_good") => &mut parts.from_good,
+                        Some("from_bad") => &mut parts.from_bad,
+                        Some("two_states") => &mut parts.two_states,
+                        _ => return Err(meta.error(
+                            "unknown part, expected `good`, `bad`, `borrow`, `borrow_mut`, \
+                            `index`, `from_good`, `from_bad` or `two_states`",
+                        )),
+                    };
+                    *part = true;
+                    Ok(())
+                })?;
+                if (parts.borrow || parts.borrow_mut) && !parts.good && !parts.bad {
+                    return Err(meta.error("`borrow` and `borrow_mut` require `good` or `bad`"));
+                }
+                only = Some(parts);
+                return Ok(());
+            }
+            Err(meta.error(
+                "unknown `#[propagate]` option on an enum, expected `exhaustive`, `termination` \
+                or `only`",
+            ))
+        })?;
+    }
+    if let Some(parts) = only {
+        options.parts = parts;
+    }
+    Ok(options)
+}
+
+/// Options of `#[propagate(...)]` on a struct
+pub struct StructOptions {
+    /// `good_if = <predicate>`, called with `&Self`
+    pub good_if: Expr,
+    /// `good = <field>`, the good payload, the whole struct when omitted
+    pub good: Option<Member>,
+    /// `bad = <field>`, the bad payload, the whole struct when omitted
+    pub bad: Option<Member>,
+}
+
+/// Parses the options of `#[propagate(...)]` on a struct, rejecting unknown or repeated ones
+pub fn get_struct_options(attrs: &[Attribute]) -> Result<StructOptions, Error> {
+    let (mut good_if, mut good, mut bad) = (None, None, None);
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident(PROPAGATE_ATTR_NAME)) {
+        attr.parse_nested_meta(|meta| {
+            let name = meta.path.get_ident().map(ToString::to_string).unwrap_or_default();
+            let duplicate = match name.as_str() {
+                "good_if" => good_if.replace(meta.value()?.parse::<Expr>()?).is_some(),
+                GOOD_ATTR_NAME => good.replace(meta.value()?.parse::<Member>()?).is_some(),
+                BAD_ATTR_NAME => bad.replace(meta.value()?.parse::<Member>()?).is_some(),
+                _ => {
+                    return Err(meta.error(
+                        "unknown `#[propagate]` option on a struct, expected `good_if`, `good` or `bad`",
+                    ))
+                }
+            };
+            if duplicate {
+                return Err(meta.error(format!("`{name}` is specified more than once")));
+            }
+            Ok(())
+        })?;
+    }
+    let good_if = good_if.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "`Propagate` on a struct needs a predicate\n\
+            help: add `#[propagate(good_if = <predicate>)]`, called with `&Self`",
+        )
+    })?;
+    Ok(StructOptions { good_if, good, bad })
+}
+
+/// Whether the variant is deliberately left neutral, with `#[neutral]` or `#[propagate(skip)]`
+pub fn is_neutral(variant: &Variant) -> Result<bool, Error> {
+    let mut neutral = None;
+    for attr in &variant.attrs {
+        if attr.path().is_ident(NEUTRAL_ATTR_NAME) {
+            attr.meta.require_path_only()?;
+            neutral = Some(attr);
+        } else if attr.path().is_ident(PROPAGATE_ATTR_NAME) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    neutral = Some(attr);
+                    return Ok(());
+                }
+                Err(meta.error("unknown `#[propagate]` option on a variant, expected `skip`"))
+            })?;
+        }
+    }
+    let Some(attr) = neutral else {
+        return Ok(false);
+    };
+    if has_good_attribute(variant) || has_bad_attribute(variant) {
+        return Err(Error::new_spanned(
+            attr,
+            "a neutral variant can't be marked `#[good]` or `#[bad]`",
+        ));
+    }
+    Ok(true)
+}
+
+/// Errors on every variant that is neither good, bad nor neutral
+pub fn validate_exhaustive(enum_name: &Ident, variants: &[Variant]) -> Result<(), Error> {
+    let errors = variants
+        .iter()
+        .filter(|v| !has_good_attribute(v) && !has_bad_attribute(v))
+        .filter(|v| !matches!(is_neutral(v), Ok(true)))
+        .map(|v| {
+            let msg = format!(
+                "variant `{}` is not classified\n\
+                help: `{enum_name}` is `#[propagate(exhaustive)]`, mark the variant `#[good]`, \
+                `#[bad]` or `#[neutral]`",
+                v.ident
+            );
+            Error::new_spanned(&v.ident, msg)
+        });
+    errors
+        .reduce(|mut errors, error| {
+            errors.combine(error);
+            errors
+        })
+        .map_or(Ok(()), Err)
+}
+
 pub fn ensure_unit_or_tuple_struct(variant: &Variant) -> bool {
     if matches!(variant.fields, Fields::Named(_)) {
         panic!("Named struct cannot have this attribute");
@@ -60,29 +327,90 @@ pub fn get_any_field_input_and_output(fields: &Fields) -> (TokenStream, TokenStr
     }
 }
 
-pub fn group_variant_ref_by_type<'a>(
-    variant: &'a [&Variant],
-) -> HashMap<&'a Fields, Vec<&'a Variant>> {
-    let mut grouped_variants: HashMap<&Fields, Vec<&Variant>> = HashMap::new();
+/// Variants grouped by payload type, in declaration order of the first variant of each group
+pub type GroupedVariants<'a> = Vec<(&'a Fields, Vec<&'a Variant>)>;
+
+pub fn group_variant_ref_by_type<'a>(variant: &'a [&Variant]) -> GroupedVariants<'a> {
+    // The map only finds the group, the `Vec` keeps the generated items in a stable order
+    let mut group_indexes: HashMap<&Fields, usize> = HashMap::new();
+    let mut grouped_variants: GroupedVariants = Vec::new();
     for variant in variant.iter() {
         let fields = &variant.fields;
-        grouped_variants.entry(fields).or_default().push(*variant);
+        let index = *group_indexes.entry(fields).or_insert_with(|| {
+            grouped_variants.push((fields, Vec::new()));
+            grouped_variants.len() - 1
+        });
+        grouped_variants[index].1.push(*variant);
     }
     grouped_variants
 }
 
 pub fn get_index_matcher(enum_name: &Ident, variants: &[Variant]) -> Vec<TokenStream> {
+    // Indexes are declaration positions, so a variant configured out leaves a gap
+    // instead of shifting the bits of the variants after it
     variants
         .iter()
         .enumerate()
         .map(|(index, variant)| {
             let variant_name = &variant.ident;
             let index = Literal::usize_unsuffixed(index);
-            quote! {#enum_name::#variant_name {..} => #index,}
+            let cfgs = get_cfg_attributes(variant);
+            quote! {#(#cfgs)* #enum_name::#variant_name {..} => #index,}
         })
         .collect()
 }
 
+pub fn get_cfg_attributes(variant: &Variant) -> Vec<&Attribute> {
+    variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .collect()
+}
+
+/// The predicate under which the variant exists, `None` if it always exists
+pub fn get_cfg_predicate(variant: &Variant) -> Option<TokenStream> {
+    let predicates: Vec<TokenStream> = get_cfg_attributes(variant)
+        .into_iter()
+        .filter_map(|attr| attr.meta.require_list().ok())
+        .map(|list| list.tokens.clone())
+        .collect();
+    match predicates.len() {
+        0 => None,
+        1 => predicates.into_iter().next(),
+        _ => Some(quote! { all(#(#predicates),*) }),
+    }
+}
+
+/// The predicate under which at least one of the variants exists, `None` if always
+pub fn get_any_cfg_predicate(variants: &[&Variant]) -> Option<TokenStream> {
+    let predicates: Option<Vec<TokenStream>> = variants.iter().map(|v| get_cfg_predicate(v)).collect();
+    predicates.map(|predicates| quote! { any(#(#predicates),*) })
+}
+
+/// The predicate under which all of `present` exist and none of `absent` exist.
+///
+/// Returns `Err(())` if that can never happen because one of `absent` always exists,
+/// and `Ok(None)` if it always happens.
+pub fn get_exact_cfg_predicate(
+    present: &[&Variant],
+    absent: &[&Variant],
+) -> Result<Option<TokenStream>, ()> {
+    let mut predicates: Vec<TokenStream> = present.iter().filter_map(|v| get_cfg_predicate(v)).collect();
+    if !absent.is_empty() {
+        let any_absent = get_any_cfg_predicate(absent).ok_or(())?;
+        predicates.push(quote! { not(#any_absent) });
+    }
+    match predicates.len() {
+        0 => Ok(None),
+        _ => Ok(Some(quote! { all(#(#predicates),*) })),
+    }
+}
+
+pub fn cfg_attribute(predicate: &Option<TokenStream>) -> Option<TokenStream> {
+    predicate.as_ref().map(|predicate| quote! { #[cfg(#predicate)] })
+}
+
 pub fn validate_grouped_variants<'a, I>(variants: I) -> Result<(), Vec<&'a Type>>
 where
     I: Iterator<Item = &'a &'a Fields>,
@@ -95,17 +423,16 @@ where
             continue;
         };
         let fields: Vec<&Field> = fields.unnamed.iter().collect();
-        let types: Vec<&Type>;
-        if fields.len() == 1 {
+        let types: Vec<&Type> = if fields.len() == 1 {
             let tuple_ty = if let Type::Tuple(tuple_ty) = &fields[0].ty {
                 tuple_ty
             } else {
                 continue;
             };
-            types = tuple_ty.elems.iter().collect::<Vec<_>>();
+            tuple_ty.elems.iter().collect::<Vec<_>>()
         } else {
-            types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
-        }
+            fields.iter().map(|field| &field.ty).collect::<Vec<_>>()
+        };
         if tuple_like_type_set.contains(&types) {
             return Err(types);
         } else {
@@ -117,8 +444,333 @@ where
 
 pub fn get_result_type(field_type: &TokenStream2, is_good: bool) -> TokenStream2 {
     if is_good {
-        quote! {Result<#field_type, Self>}
+        quote! {::core::result::Result<#field_type, Self>}
     } else {
-        quote! {Result<Self, #field_type>}
+        quote! {::core::result::Result<Self, #field_type>}
+    }
+}
+
+/// Picks a lifetime for the `&` and `&mut` impls that is not declared by the enum
+pub fn fresh_lifetime(generics: &Generics) -> Lifetime {
+    let is_taken = |name: &str| {
+        generics
+            .lifetimes()
+            .any(|param| param.lifetime.ident == name)
+    };
+    let mut name = String::from("p");
+    let mut suffix = 0usize;
+    while is_taken(&name) {
+        name = String::from("p") + &suffix.to_string();
+        suffix += 1;
+    }
+    Lifetime::new(&(String::from("'") + &name), Span::call_site())
+}
+
+/// The type a variant hands out as its inner value, owned
+fn payload_type(fields: &Fields) -> Type {
+    match fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => fields.unnamed[0].ty.clone(),
+        Fields::Unnamed(fields) => Type::Tuple(TypeTuple {
+            paren_token: Default::default(),
+            elems: fields.unnamed.iter().map(|field| field.ty.clone()).collect(),
+        }),
+        _ => Type::Tuple(TypeTuple {
+            paren_token: Default::default(),
+            elems: Punctuated::new(),
+        }),
+    }
+}
+
+/// The type parameter a type names, if it is one
+fn as_type_param<'a>(ty: &Type, type_params: &[&'a Ident]) -> Option<&'a Ident> {
+    match ty {
+        Type::Path(ty) if ty.qself.is_none() => {
+            let ident = ty.path.get_ident()?;
+            type_params.iter().copied().find(|param| *param == ident)
+        }
+        _ => None,
+    }
+}
+
+/// Type parameters bound while unifying two payload types. Both payloads belong to the same
+/// enum, so a parameter stands for the same type on both sides.
+struct Substitution<'a> {
+    type_params: &'a [&'a Ident],
+    bound: Vec<(&'a Ident, Type)>,
+}
+
+impl<'a> Substitution<'a> {
+    /// Follows the bindings of a type parameter
+    fn resolve(&self, ty: &Type) -> Type {
+        let mut ty = ty.clone();
+        while let Some(param) = as_type_param(&ty, self.type_params) {
+            match self.bound.iter().find(|(bound, _)| *bound == param) {
+                Some((_, bound_ty)) => ty = bound_ty.clone(),
+                None => break,
+            }
+        }
+        ty
+    }
+
+    /// Whether `param` appears in `ty`, so binding it to `ty` would make an infinite type
+    fn occurs(&self, param: &Ident, ty: &Type) -> bool {
+        let ty = self.resolve(ty);
+        if as_type_param(&ty, self.type_params).is_some_and(|other| other == param) {
+            return true;
+        }
+        let mut occurs = false;
+        let mut visit = |ty: &Type| occurs |= self.occurs(param, ty);
+        match &ty {
+            Type::Paren(ty) => visit(&ty.elem),
+            Type::Group(ty) => visit(&ty.elem),
+            Type::Reference(ty) => visit(&ty.elem),
+            Type::Ptr(ty) => visit(&ty.elem),
+            Type::Slice(ty) => visit(&ty.elem),
+            Type::Array(ty) => visit(&ty.elem),
+            Type::Tuple(ty) => ty.elems.iter().for_each(visit),
+            Type::Path(ty) => {
+                let args = ty.path.segments.iter().filter_map(|seg| match &seg.arguments {
+                    PathArguments::AngleBracketed(args) => Some(&args.args),
+                    _ => None,
+                });
+                args.flatten().for_each(|arg| {
+                    if let GenericArgument::Type(ty) = arg {
+                        visit(ty);
+                    }
+                });
+            }
+            _ => {}
+        }
+        occurs
+    }
+
+    /// Whether some choice of generic arguments could make both types equal
+    ///
+    /// Lifetimes and const arguments are assumed to always unify, and projections like
+    /// `<T as Trait>::Output` may be anything, so this errs on the side of reporting an overlap.
+    fn unify(&mut self, a: &Type, b: &Type) -> bool {
+        let (a, b) = (self.resolve(a), self.resolve(b));
+        match (&a, &b) {
+            (Type::Paren(a), _) => self.unify(&a.elem, &b),
+            (_, Type::Paren(b)) => self.unify(&a, &b.elem),
+            (Type::Group(a), _) => self.unify(&a.elem, &b),
+            (_, Type::Group(b)) => self.unify(&a, &b.elem),
+            _ => match (
+                as_type_param(&a, self.type_params),
+                as_type_param(&b, self.type_params),
+            ) {
+                (Some(a), Some(b)) if a == b => true,
+                (Some(param), _) => self.bind(param, &b),
+                (_, Some(param)) => self.bind(param, &a),
+                (None, None) => self.unify_structure(&a, &b),
+            },
+        }
+    }
+
+    fn bind(&mut self, param: &'a Ident, ty: &Type) -> bool {
+        if self.occurs(param, ty) {
+            return false;
+        }
+        self.bound.push((param, ty.clone()));
+        true
+    }
+
+    fn unify_structure(&mut self, a: &Type, b: &Type) -> bool {
+        match (a, b) {
+            (Type::Path(a), _) if a.qself.is_some() => true,
+            (_, Type::Path(b)) if b.qself.is_some() => true,
+            (Type::Path(a), Type::Path(b)) => {
+                a.path.segments.len() == b.path.segments.len()
+                    && a.path
+                        .segments
+                        .iter()
+                        .zip(b.path.segments.iter())
+                        .all(|(a, b)| a.ident == b.ident && self.unify_arguments(&a.arguments, &b.arguments))
+            }
+            (Type::Reference(a), Type::Reference(b)) => {
+                a.mutability.is_some() == b.mutability.is_some() && self.unify(&a.elem, &b.elem)
+            }
+            (Type::Ptr(a), Type::Ptr(b)) => {
+                a.mutability.is_some() == b.mutability.is_some() && self.unify(&a.elem, &b.elem)
+            }
+            (Type::Tuple(a), Type::Tuple(b)) => {
+                a.elems.len() == b.elems.len()
+                    && a.elems
+                        .iter()
+                        .zip(b.elems.iter())
+                        .all(|(a, b)| self.unify(a, b))
+            }
+            (Type::Slice(a), Type::Slice(b)) => self.unify(&a.elem, &b.elem),
+            (Type::Array(a), Type::Array(b)) => self.unify(&a.elem, &b.elem),
+            _ => a.to_token_stream().to_string() == b.to_token_stream().to_string(),
+        }
+    }
+
+    fn unify_arguments(&mut self, a: &PathArguments, b: &PathArguments) -> bool {
+        match (a, b) {
+            (PathArguments::None, PathArguments::None) => true,
+            (PathArguments::AngleBracketed(a), PathArguments::AngleBracketed(b)) => {
+                a.args.len() == b.args.len()
+                    && a.args.iter().zip(b.args.iter()).all(|(a, b)| match (a, b) {
+                        (GenericArgument::Type(a), GenericArgument::Type(b)) => self.unify(a, b),
+                        (GenericArgument::Lifetime(_), GenericArgument::Lifetime(_)) => true,
+                        (GenericArgument::Const(_), _) | (_, GenericArgument::Const(_)) => true,
+                        _ => a.to_token_stream().to_string() == b.to_token_stream().to_string(),
+                    })
+            }
+            _ => a.to_token_stream().to_string() == b.to_token_stream().to_string(),
+        }
+    }
+}
+
+/// A type as written in source, without the spaces `to_string` puts between all tokens
+pub fn display_type(ty: &Type) -> String {
+    let mut out = String::new();
+    let mut previous: Option<TokenTree> = None;
+    display_tokens(ty.to_token_stream(), &mut out, &mut previous);
+    out
+}
+
+fn display_tokens(tokens: TokenStream2, out: &mut String, previous: &mut Option<TokenTree>) {
+    for token in tokens {
+        let punct = |token: &TokenTree, chars: &str| {
+            matches!(token, TokenTree::Punct(punct) if chars.contains(punct.as_char()))
+        };
+        let word = |token: &TokenTree| matches!(token, TokenTree::Ident(_) | TokenTree::Literal(_));
+        let space = previous.as_ref().is_some_and(|previous| {
+            // `dyn Trait`, `T, U`, `[T; 3]`, `A + B` and `fn() -> T`
+            word(previous) && word(&token)
+                || punct(previous, ",;+")
+                || punct(&token, "+-")
+                || punct(previous, ">") && out.ends_with("->")
+        });
+        if space {
+            out.push(' ');
+        }
+        match &token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{ ", " }"),
+                    Delimiter::None => ("", ""),
+                };
+                out.push_str(open);
+                display_tokens(group.stream(), out, &mut None);
+                out.push_str(close);
+            }
+            token => out.push_str(&token.to_string()),
+        }
+        *previous = Some(token);
+    }
+}
+
+/// Rejects two differently typed `#[good]` (or `#[bad]`) variants whose payloads may be the
+/// same type, e.g. `A(T)` and `B(U)`, because their impls would conflict
+pub fn validate_overlapping_payloads(
+    grouped_variants: &GroupedVariants,
+    all_variants: &[Variant],
+    generics: &Generics,
+    attr_name: &str,
+) -> Result<(), Error> {
+    let type_params: Vec<&Ident> = generics.type_params().map(|param| &param.ident).collect();
+    if type_params.is_empty() {
+        return Ok(());
+    }
+    let position = |variant: &Variant| {
+        all_variants
+            .iter()
+            .position(|v| core::ptr::eq(v, variant))
+    };
+    let mut groups: Vec<(Type, &Variant)> = grouped_variants
+        .iter()
+        .map(|(fields, variants)| (payload_type(fields), variants[0]))
+        .collect();
+    // Report on the variant declared last
+    groups.sort_by_key(|(_, variant)| position(variant));
+    for (index, (ty, variant)) in groups.iter().enumerate() {
+        for (other_ty, other_variant) in &groups[index + 1..] {
+            let mut substitution = Substitution {
+                type_params: &type_params,
+                bound: Vec::new(),
+            };
+            if !substitution.unify(ty, other_ty) {
+                continue;
+            }
+            let (first, second) = ((ty, variant), (other_ty, other_variant));
+            let msg = format!(
+                "`#[{attr_name}]` variants `{}` and `{}` have payload types `{}` and `{}`, \
+                which are the same type for some generic arguments, so their impls would conflict.\n\
+                Mark only one of them `#[{attr_name}]`, or wrap one payload in a newtype",
+                first.1.ident,
+                second.1.ident,
+                display_type(first.0),
+                display_type(second.0),
+            );
+            return Err(Error::new_spanned(&second.1.fields, msg));
+        }
+    }
+    Ok(())
+}
+
+/// Standard macros whose arguments are comma separated expressions, evaluated in place
+const EXPR_ARG_MACROS: &[&str] = &[
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "dbg",
+    "eprint",
+    "eprintln",
+    "format",
+    "format_args",
+    "panic",
+    "print",
+    "println",
+    "todo",
+    "unimplemented",
+    "unreachable",
+    "vec",
+    "write",
+    "writeln",
+];
+
+/// Visits the arguments of the standard macros taking comma separated expressions, like
+/// `assert_eq!` or `format!`. Other macros are opaque and left alone, even when their body
+/// happens to parse as expressions, since they may not evaluate them as written.
+pub fn visit_macro_args(visitor: &mut impl VisitMut, mac: &mut Macro) {
+    let takes_exprs = mac
+        .path
+        .segments
+        .last()
+        .is_some_and(|seg| EXPR_ARG_MACROS.iter().any(|name| seg.ident == name));
+    if !takes_exprs {
+        return;
+    }
+    let parser = Punctuated::<Expr, Token![,]>::parse_terminated;
+    let Ok(mut args) = mac.parse_body_with(parser) else {
+        return;
+    };
+    for arg in args.iter_mut() {
+        visitor.visit_expr_mut(arg);
+    }
+    mac.tokens = args.into_token_stream();
+}
+
+/// Splits off the last token, a `{ ... }` group like a loop body, since the expression before it
+/// would otherwise parse it as a struct literal
+pub fn split_body(input: TokenStream2) -> syn::Result<(TokenStream2, TokenTree)> {
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    match tokens.pop() {
+        Some(TokenTree::Group(body)) if body.delimiter() == Delimiter::Brace => {
+            Ok((tokens.into_iter().collect(), TokenTree::Group(body)))
+        }
+        Some(last) => Err(Error::new(
+            last.span(),
+            "expected a `{ ... }` block at the end",
+        )),
+        None => Err(Error::new(Span::call_site(), "expected a `{ ... }` block")),
     }
 }
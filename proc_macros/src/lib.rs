@@ -1,7 +1,15 @@
 #![no_std]
 
+mod attempt;
 mod bool_packing;
+mod derive_struct;
+mod first_good;
+mod function;
 mod helper_fn;
+mod looping;
+mod match_good;
+mod propagation;
+mod retry;
 
 extern crate alloc;
 
@@ -11,7 +19,10 @@ use alloc::vec::Vec;
 use proc_macro::TokenStream;
 use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
 use quote::{quote, ToTokens};
-use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Generics, Token, Type, Variant};
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Fields, GenericParam, LifetimeParam, Token, Type,
+    Variant,
+};
 
 use helper_fn::*;
 use syn::punctuated::Punctuated;
@@ -50,7 +61,7 @@ use syn::punctuated::Punctuated;
 /// You can have multiple `#[good]` or `#[bad]` attributes for multiple variants with the
 /// same type.
 /// - Note: Deriving `Propagate` also implements `FromGood` and `FromBad`, **overloaded types
-/// loses this trait**
+///   loses this trait**
 /// ```rust ignore
 /// use propagate::{Propagate, FromBad, bad};
 /// #[derive(Propagate)]
@@ -126,39 +137,175 @@ use syn::punctuated::Punctuated;
 ///     port
 /// }
 /// ```
-#[proc_macro_derive(Propagate, attributes(good, bad))]
+///
+/// # Retryable variants
+/// Variants marked `#[bad(retryable)]` implement `Retryable`, so `retry!` evaluates the
+/// expression again while it gives one of them.
+/// ```rust ignore
+/// use propagate::Propagate;
+/// #[derive(Propagate)]
+/// enum Transfer {
+///     #[good]
+///     Done(usize),
+///     #[bad(retryable)]
+///     Busy,
+///     #[bad]
+///     Failed(u8),
+/// }
+/// ```
+///
+/// # Exhaustive enums
+/// With `#[propagate(exhaustive)]` on the enum, every variant must be marked `#[good]`, `#[bad]`
+/// or `#[neutral]`, so a new variant can't be propagated as neither by accident.
+/// `#[propagate(skip)]` is the same as `#[neutral]`. Neutral variants are reported by
+/// `NeutralIndex` and `is_neutral!`.
+/// ```rust ignore
+/// use propagate::Propagate;
+/// #[derive(Propagate)]
+/// #[propagate(exhaustive)]
+/// enum Packet {
+///     #[good]
+///     Data(Vec<u8>),
+///     #[bad]
+///     Corrupt(u32),
+///     #[neutral]
+///     KeepAlive,
+///     #[propagate(skip)]
+///     Padding(usize),
+/// }
+/// ```
+///
+/// # Termination
+/// With `#[propagate(termination)]` on the enum, it implements `std::process::Termination`, so
+/// `main` can return it and `good!(step)` propagates a bad step out of `main`. Bad variants are
+/// printed to stderr with `Debug`, or `Display` with `#[propagate(termination(display))]`, and
+/// exit with `exit_code`, `1` by default. Other variants exit with their `exit_code`, `0` by
+/// default: `good!` only propagates bad variants out of `main`, so like the `?` operator, any
+/// other value means `main` ran to its end. A variant that should fail the process must be
+/// marked `#[bad]`.
+/// ```rust ignore
+/// use propagate::{good, Propagate};
+/// #[derive(Debug, Propagate)]
+/// #[propagate(termination)]
+/// enum Status {
+///     #[good]
+///     Done,
+///     #[good(exit_code = 3)]
+///     NothingToDo,
+///     #[bad(exit_code = 2)]
+///     Usage(String),
+///     #[bad]
+///     Failed(std::io::Error),
+/// }
+///
+/// fn main() -> Status {
+///     good!(parse_args());
+///     run()
+/// }
+/// ```
+///
+/// # Picking the impls
+/// By default the derive implements everything it can. With `#[propagate(only(...))]` it only
+/// implements the listed parts, so an enum with its own `From`-style constructors, or one that
+/// must not hand out `&mut` access to its payloads, takes just what it needs:
+/// - `good`, `bad`: `Good` and `Bad` for the enum
+/// - `borrow`, `borrow_mut`: the same for `&Enum` and `&mut Enum`
+/// - `from_good`, `from_bad`: `FromGood` and `FromBad`
+/// - `two_states`: `ExactlyTwoDistinctVariants`, which makes the enum `TwoStates`
+/// - `index`: the indexes used by `is_good!`, `is_bad!` and `is_neutral!`
+/// ```rust ignore
+/// use propagate::Propagate;
+/// #[derive(Propagate)]
+/// #[propagate(only(good, bad, borrow, two_states))]
+/// enum Guarded {
+///     #[good]
+///     Value(u32),
+///     #[bad]
+///     Error(String),
+/// }
+/// ```
+///
+/// # Structs
+/// A struct is classified by its data instead of a variant: it is good when the
+/// `good_if` predicate, called with `&Self`, holds, and bad otherwise. `good = <field>` and
+/// `bad = <field>` pick the payloads, the whole struct is the payload when omitted.
+/// A struct is always two-state.
+/// ```rust ignore
+/// use propagate::Propagate;
+/// #[derive(Propagate)]
+/// #[propagate(good_if = Self::is_success)]
+/// struct Status(u16);
+///
+/// #[derive(Propagate)]
+/// #[propagate(good_if = |reply: &Reply| reply.code == 0, good = body, bad = code)]
+/// struct Reply {
+///     code: i32,
+///     body: Vec<u8>,
+/// }
+/// ```
+///
+/// # Generics
+/// Generic enums are supported, including bounds, defaults and const generics.
+/// Two `#[good]` (or two `#[bad]`) variants must not carry payloads that could be the same
+/// type, like `Left(T)` and `Right(U)`, since `Good<T>` and `Good<U>` would overlap.
+/// ```rust ignore
+/// use propagate::Propagate;
+/// #[derive(Propagate)]
+/// enum Frame<T: Clone = u8, const N: usize = 4> {
+///     #[good]
+///     Full([T; N]),
+///     #[bad]
+///     Partial(usize),
+/// }
+/// ```
+///
+/// # Conditional variants
+/// Variants behind `#[cfg(...)]` are supported. `FromGood`/`FromBad` and `TwoStates` are
+/// implemented under exactly the configurations where their requirements hold.
+/// ```rust ignore
+/// use propagate::Propagate;
+/// #[derive(Propagate)]
+/// enum Reply {
+///     #[good]
+///     Text(String),
+///     // `Reply` is a two-state enum unless the `binary` feature is enabled
+///     #[cfg(feature = "binary")]
+///     Binary(Vec<u8>),
+///     #[bad]
+///     Error(u32),
+/// }
+/// ```
+#[proc_macro_derive(Propagate, attributes(good, bad, neutral, propagate))]
 pub fn derive_propagate(input: TokenStream) -> TokenStream {
     let trait_path = quote! {::propagate::};
 
     let DeriveInput {
+        attrs,
         data,
         ident,
         generics,
         ..
     } = parse_macro_input!(input);
 
-    let variants: Vec<Variant> = if let Data::Enum(data) = data {
-        data.variants.into_iter().collect()
-    } else {
-        return quote! { compile_error!("`Propagate` can only be derived for enums"); }.into();
+    let variants: Vec<Variant> = match data {
+        Data::Enum(data) => data.variants.into_iter().collect(),
+        Data::Struct(data) => {
+            return get_struct_options(&attrs)
+                .and_then(|options| {
+                    derive_struct::derive_struct(&trait_path, &ident, &generics, &data.fields, options)
+                })
+                .unwrap_or_else(Error::into_compile_error)
+                .into();
+        }
+        Data::Union(_) => {
+            return quote! { ::core::compile_error!("`Propagate` can only be derived for enums and structs"); }.into();
+        }
     };
 
     if variants.is_empty() {
-        return quote! { compile_error!("`Propagate` cannot be derived for enums without fields"); }.into()
+        return quote! { ::core::compile_error!("`Propagate` cannot be derived for enums without fields"); }.into()
     }
 
-    let Generics {
-        params,
-        where_clause,
-        lt_token,
-        gt_token,
-    } = generics;
-    let trailing_comma = if params.is_empty() {
-        quote! {}
-    } else {
-        quote! {,}
-    };
-
     let good_variants: Vec<&Variant> = variants
         .iter()
         .filter(|v| has_good_attribute(v))
@@ -172,7 +319,7 @@ pub fn derive_propagate(input: TokenStream) -> TokenStream {
 
     if good_variants.is_empty() && bad_variants.is_empty() {
         return quote! {
-            compile_error!(
+            ::core::compile_error!(
             "Enum must contain at least one `#[good]` or `#[bad]` attribute. \
                 Did you forget to mark a good or bad variant?"
             );
@@ -192,27 +339,85 @@ pub fn derive_propagate(input: TokenStream) -> TokenStream {
     let grouped_variants_iter = grouped_good_variants_iter.chain(grouped_bad_variants_iter);
 
     match (
-        validate_grouped_variants(grouped_good_variants.keys()),
-        validate_grouped_variants(grouped_bad_variants.keys()),
+        validate_grouped_variants(grouped_good_variants.iter().map(|(fields, _)| fields)),
+        validate_grouped_variants(grouped_bad_variants.iter().map(|(fields, _)| fields)),
     ) {
         (Ok(_), Ok(_)) => {}
         (_, Err(types)) | (Err(types), _) => {
-            let p: Punctuated<&Type, Token![,]> = Punctuated::from_iter(types.into_iter());
+            let p: Punctuated<&Type, Token![,]> = Punctuated::from_iter(types);
             let types = p.to_token_stream().to_string();
             let msg = format!(
                 "Types `({types})` and `{types}` are ambiguous. \
-                Cannot infer types for both tuple and n-args variants.\n");
+                Cannot infer types for both tuple and n-args variants.");
 
             let error = Error::new(Span::call_site(), msg);
             return error.into_compile_error().into();
         }
     }
 
-    let lifetime = quote! {'p};
+    if let Err(error) =
+        validate_overlapping_payloads(&grouped_good_variants, &variants, &generics, GOOD_ATTR_NAME)
+            .and_then(|_| {
+                validate_overlapping_payloads(&grouped_bad_variants, &variants, &generics, BAD_ATTR_NAME)
+            })
+    {
+        return error.into_compile_error().into();
+    }
+
+    let enum_options = match get_enum_options(&attrs) {
+        Ok(options) => options,
+        Err(error) => return error.into_compile_error().into(),
+    };
+    let mut variant_options: Vec<VariantOptions> = Vec::new();
+    for variant in &variants {
+        match get_variant_options(variant) {
+            Ok(options) => variant_options.push(options),
+            Err(error) => return error.into_compile_error().into(),
+        }
+    }
+    let retryable_variants: Vec<&Variant> = variants
+        .iter()
+        .zip(&variant_options)
+        .filter(|(_, options)| options.retryable)
+        .map(|(variant, _)| variant)
+        .collect();
+    if enum_options.termination.is_none() {
+        if let Some(exit_code) = variant_options.iter().find_map(|options| options.exit_code.as_ref()) {
+            let msg = "`exit_code` requires `#[propagate(termination)]` on the enum";
+            return Error::new_spanned(exit_code, msg).into_compile_error().into();
+        }
+    }
+
+    let mut neutral_attribute: Vec<bool> = Vec::new();
+    for variant in &variants {
+        match is_neutral(variant) {
+            Ok(neutral) => neutral_attribute.push(neutral),
+            Err(error) => return error.into_compile_error().into(),
+        }
+    }
+    if enum_options.exhaustive {
+        if let Err(error) = validate_exhaustive(&ident, &variants) {
+            return error.into_compile_error().into();
+        }
+    }
+
+    // The lifetime of the `&` and `&mut` impls must not shadow a lifetime of the enum
+    let lifetime = fresh_lifetime(&generics);
+    let mut generics_ref = generics.clone();
+    generics_ref
+        .params
+        .insert(0, GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (impl_generics_ref, _, _) = generics_ref.split_for_impl();
+
     let (borrow, borrow_mut, owned) = (quote! {& #lifetime}, quote! {& #lifetime mut}, quote! {});
-    let generic_ref = quote! {<#lifetime #trailing_comma #params>};
-    let generic = quote! {#lt_token #params #gt_token};
-    let impls = grouped_variants_iter.clone().map(|(is_good, fields, variants)| {
+    // Absolute paths, the caller may shadow `Ok` and `Err` or have no prelude
+    let (ok, err) = (quote! {::core::result::Result::Ok}, quote! {::core::result::Result::Err});
+    let parts = enum_options.parts;
+    let impls = grouped_variants_iter.clone().filter(|(is_good, _, _)| match is_good {
+        true => parts.good,
+        false => parts.bad,
+    }).map(|(is_good, fields, variants)| {
         let field_type_ref = get_tuple_field_type(fields, &borrow);
         let field_type_mut = get_tuple_field_type(fields, &borrow_mut);
         let field_type = get_tuple_field_type(fields, &owned);
@@ -222,14 +427,25 @@ pub fn derive_propagate(input: TokenStream) -> TokenStream {
         let result_type = get_result_type(&field_type, is_good);
 
         let (trait_name, method, keep_variant, dump_variant) =
-            if is_good { (quote! {#trait_path Good}, quote! {good}, quote! {Ok}, quote! {Err})}
-            else { (quote! {#trait_path Bad}, quote! {bad}, quote! {Err}, quote! {Ok})};
+            if is_good { (quote! {#trait_path Good}, quote! {good}, ok.clone(), err.clone())}
+            else { (quote! {#trait_path Bad}, quote! {bad}, err.clone(), ok.clone())};
 
         let (input, output) = get_any_field_input_and_output(fields);
-        let match_rules = variants.iter().map(|v| {
+        // Variants without `#[cfg]` share one or-pattern arm, `#[cfg]` can't go on an alternative
+        let (conditional, unconditional): (Vec<&Variant>, Vec<&Variant>) =
+            variants.iter().partition(|v| get_cfg_predicate(v).is_some());
+        let unconditional_rule = (!unconditional.is_empty()).then(|| {
+            let variant_names = unconditional.iter().map(|v| &v.ident);
+            quote! { #(#ident::#variant_names #input)|* => #keep_variant(#output), }
+        });
+        let conditional_rules = conditional.iter().map(|v| {
             let variant_name = &v.ident;
-            quote! { #ident::#variant_name #input => #keep_variant(#output), }
+            let cfgs = get_cfg_attributes(v);
+            quote! { #(#cfgs)* #ident::#variant_name #input => #keep_variant(#output), }
         });
+        let match_rules = unconditional_rule.into_iter().chain(conditional_rules);
+        // The payload type may only exist when one of the variants does
+        let cfg = cfg_attribute(&get_any_cfg_predicate(variants));
         let body = quote! {
             match self {
                 #(#match_rules)*
@@ -237,35 +453,43 @@ pub fn derive_propagate(input: TokenStream) -> TokenStream {
             }
         };
         let impl_owned = quote! {
-                impl #generic #trait_name <#field_type> for #ident #generic #where_clause {
+                #cfg
+                impl #impl_generics #trait_name <#field_type> for #ident #ty_generics #where_clause {
                     fn #method (self) -> #result_type {
                         #body
                     }
                 }
             };
-        match fields {
-            Fields::Unit => impl_owned,
-            _ =>
-            quote! {
-                impl #generic_ref #trait_name <#field_type_ref> for & #lifetime #ident #generic #where_clause {
-                    fn #method (self) -> #result_type_ref {
-                        #body
-                    }
+        if let Fields::Unit = fields {
+            return impl_owned;
+        }
+        let impl_ref = parts.borrow.then(|| quote! {
+            #cfg
+            impl #impl_generics_ref #trait_name <#field_type_ref> for & #lifetime #ident #ty_generics #where_clause {
+                fn #method (self) -> #result_type_ref {
+                    #body
                 }
-                impl #generic_ref #trait_name <#field_type_mut> for & #lifetime mut #ident #generic #where_clause {
-                    fn #method (self) -> #result_type_mut {
-                        #body
-                    }
+            }
+        });
+        let impl_mut = parts.borrow_mut.then(|| quote! {
+            #cfg
+            impl #impl_generics_ref #trait_name <#field_type_mut> for & #lifetime mut #ident #ty_generics #where_clause {
+                fn #method (self) -> #result_type_mut {
+                    #body
                 }
-                #impl_owned
             }
+        });
+        quote! {
+            #impl_ref
+            #impl_mut
+            #impl_owned
         }
 
     });
 
     let index_matcher: Vec<TokenStream2> = get_index_matcher(&ident, &variants);
     let get_index_impl = quote! {
-        impl #generic #trait_path __private::__GetIndex for #ident #generic #where_clause {
+        impl #impl_generics #trait_path __private::__GetIndex for #ident #ty_generics #where_clause {
             fn get_index(&self) -> usize {
                 match self {
                     #(#index_matcher)*
@@ -281,7 +505,7 @@ pub fn derive_propagate(input: TokenStream) -> TokenStream {
         .map(|num| Literal::u8_unsuffixed(*num))
         .collect();
     let good_index_impl = quote! {
-        impl #generic #trait_path __private::__GoodIndex for #ident #generic #where_clause {
+        impl #impl_generics #trait_path __private::__GoodIndex for #ident #ty_generics #where_clause {
             fn good_indexes(&self) -> &'static [u8] {
                 &[#(#good_packed_lit),*]
             }
@@ -295,18 +519,51 @@ pub fn derive_propagate(input: TokenStream) -> TokenStream {
         .map(|num| Literal::u8_unsuffixed(*num))
         .collect();
     let bad_index_impl = quote! {
-        impl #generic #trait_path __private::__BadIndex for #ident #generic #where_clause {
+        impl #impl_generics #trait_path __private::__BadIndex for #ident #ty_generics #where_clause {
             fn bad_indexes(&self) -> &'static [u8] {
                 &[#(#bad_packed_lit),*]
             }
         }
     };
 
-    let from_good_bad_impls = grouped_variants_iter.clone()
-        .filter(|(_, _, variants)| variants.len() == 1)
-        .map(|(is_good, fields, variants)| {
+    let neutral_packed = bool_packing::pack_bool(neutral_attribute.into_iter());
+    let neutral_packed_lit: Vec<Literal> = neutral_packed
+        .iter()
+        .map(|num| Literal::u8_unsuffixed(*num))
+        .collect();
+    let neutral_index_impl = quote! {
+        impl #impl_generics #trait_path __private::__NeutralIndex for #ident #ty_generics #where_clause {
+            fn neutral_indexes(&self) -> &'static [u8] {
+                &[#(#neutral_packed_lit),*]
+            }
+        }
+    };
+
+    // Overloaded types lose `FromGood`/`FromBad`, unless all but one variant are configured out
+    let from_good_bad_variants = grouped_variants_iter.clone().filter(|(is_good, _, _)| match is_good {
+        true => parts.from_good,
+        false => parts.from_bad,
+    }).flat_map(|(is_good, fields, variants)| {
+        // A variant without `#[cfg]` always exists, so only it can be the one left
+        let unconditional = variants.iter().filter(|v| get_cfg_predicate(v).is_none()).count();
+        variants.iter().filter(move |variant| match unconditional {
+            0 => true,
+            1 => get_cfg_predicate(variant).is_none(),
+            _ => false,
+        }).filter_map(move |variant| {
+            let others: Vec<&Variant> = variants
+                .iter()
+                .filter(|other| !core::ptr::eq(**other, *variant))
+                .copied()
+                .collect();
+            let predicate = get_exact_cfg_predicate(&[variant], &others).ok()?;
+            Some((is_good, fields, *variant, predicate))
+        })
+    });
+    let from_good_bad_impls = from_good_bad_variants.map(|(is_good, fields, variant, predicate)| {
         let field_type = get_tuple_field_type(fields, &owned);
-        let variant_name = &variants[0].ident;
+        let variant_name = &variant.ident;
+        let cfg = cfg_attribute(&predicate);
         let (trait_name, method) =
             if is_good {(quote! {FromGood}, quote! {from_good})}
             else {(quote! {FromBad}, quote! {from_bad})};
@@ -326,7 +583,8 @@ pub fn derive_propagate(input: TokenStream) -> TokenStream {
         };
 
         quote! {
-            impl #generic #trait_path #trait_name <#field_type> for #ident #generic #where_clause {
+            #cfg
+            impl #impl_generics #trait_path #trait_name <#field_type> for #ident #ty_generics #where_clause {
                 fn #method(_v: #field_type) -> Self {
                     #instantiate
                 }
@@ -334,25 +592,223 @@ pub fn derive_propagate(input: TokenStream) -> TokenStream {
         }
     });
 
-    let two_states_impl: Option<_> = if grouped_good_variants.len() == 1
-        && grouped_bad_variants.len() == 1
-        && variants.len() == 2
-        && &good_packed != &bad_packed
-    {
+    // Two states hold whenever exactly one good and one bad variant exist and nothing else does
+    let mut two_states_predicates: Vec<Option<TokenStream2>> = Vec::new();
+    // Variants without `#[cfg]` always exist, more than two of them rule out every pair
+    let unconditional = variants.iter().filter(|v| get_cfg_predicate(v).is_none()).count();
+    let pairs = if unconditional > 2 { [].iter() } else { good_variants.iter() };
+    for good_variant in pairs.filter(|v| !has_bad_attribute(v)) {
+        for bad_variant in bad_variants.iter().filter(|v| !has_good_attribute(v)) {
+            let others: Vec<&Variant> = variants
+                .iter()
+                .filter(|v| !core::ptr::eq(*v, *good_variant) && !core::ptr::eq(*v, *bad_variant))
+                .collect();
+            if let Ok(predicate) = get_exact_cfg_predicate(&[good_variant, bad_variant], &others) {
+                two_states_predicates.push(predicate);
+            }
+        }
+    }
+    let two_states_impl: Option<_> = if !parts.two_states || two_states_predicates.is_empty() {
+        None
+    } else {
+        let predicates: Option<Vec<TokenStream2>> = two_states_predicates.into_iter().collect();
+        let cfg = predicates.map(|predicates| quote! { #[cfg(any(#(#predicates),*))] });
         Some(
-            quote! {unsafe impl #generic #trait_path ExactlyTwoDistinctVariants for #ident #generic #where_clause {}},
+            quote! {#cfg unsafe impl #impl_generics #trait_path ExactlyTwoDistinctVariants for #ident #ty_generics #where_clause {}},
         )
-    } else {
-        None
     };
 
-    let output = quote! {
-        #(#impls)*
+    let retryable_impl = (!retryable_variants.is_empty()).then(|| {
+        let arms = retryable_variants.iter().map(|variant| {
+            let variant_name = &variant.ident;
+            let cfgs = get_cfg_attributes(variant);
+            quote! { #(#cfgs)* #ident::#variant_name { .. } => true, }
+        });
+        quote! {
+            impl #impl_generics #trait_path Retryable for #ident #ty_generics #where_clause {
+                fn is_retryable(&self) -> bool {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        #(#arms)*
+                        _ => false,
+                    }
+                }
+            }
+        }
+    });
+
+    let termination_impl = enum_options.termination.map(|report| {
+        let arms = variants.iter().zip(&variant_options).map(|(variant, options)| {
+            let variant_name = &variant.ident;
+            let cfgs = get_cfg_attributes(variant);
+            let exit_code = options
+                .exit_code
+                .as_ref()
+                .map(|code| quote! { ::std::process::ExitCode::from(#code) });
+            let arm = if has_bad_attribute(variant) {
+                let exit_code = exit_code.unwrap_or(quote! { ::std::process::ExitCode::FAILURE });
+                let format = match report {
+                    Report::Debug => quote! { "Error: {:?}" },
+                    Report::Display => quote! { "Error: {}" },
+                };
+                quote! {{
+                    ::std::eprintln!(#format, self);
+                    #exit_code
+                }}
+            } else {
+                exit_code.unwrap_or(quote! { ::std::process::ExitCode::SUCCESS })
+            };
+            quote! { #(#cfgs)* #ident::#variant_name { .. } => #arm, }
+        });
+        let bound = match report {
+            Report::Debug => quote! { ::core::fmt::Debug },
+            Report::Display => quote! { ::core::fmt::Display },
+        };
+        // Without generics the bound would be trivial, which is only allowed on nightly
+        let mut generics = generics.clone();
+        if !generics.params.is_empty() {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote! { Self: #bound });
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        quote! {
+            impl #impl_generics ::std::process::Termination for #ident #ty_generics #where_clause {
+                fn report(self) -> ::std::process::ExitCode {
+                    match &self {
+                        #(#arms)*
+                    }
+                }
+            }
+        }
+    });
+
+    let index_impls = parts.index.then(|| quote! {
         #get_index_impl
         #good_index_impl
         #bad_index_impl
+        #neutral_index_impl
+    });
+
+    let output = quote! {
+        #(#impls)*
+        #index_impls
         #(#from_good_bad_impls)*
         #two_states_impl
+        #retryable_impl
+        #termination_impl
     };
     output.into()
 }
+
+/// Makes `?` work on any two-state enum in the body of a function.
+///
+/// Each `expr?` becomes `good!(expr => Bad)`: the good value is kept, and the bad value is
+/// converted to the return type through `FromBad`. The tail expression of the function is wrapped
+/// through `FromGood`, so `Result`, `Option`, `ControlFlow` and derived enums mix in one function.
+/// Like any `=>` clause, this requires `expr` to be a `TwoStates` enum, with exactly one good and
+/// one bad variant.
+///
+/// Unlike the `?` operator, bad values are not converted through `From`. `?` in closures, async
+/// blocks and nested items is left alone, and a body without a tail expression is not wrapped.
+/// In macro calls, only the arguments of standard macros like `format!`, `assert_eq!`, `vec!` or
+/// `write!` are rewritten, and `?` in other macros keeps its usual meaning.
+/// ```rust ignore
+/// use propagate::Propagate;
+/// #[derive(Propagate)]
+/// enum Lookup {
+///     #[good]
+///     Found(u32),
+///     #[bad]
+///     Missing,
+/// }
+///
+/// #[propagate::function]
+/// fn total(a: Lookup, b: Option<u32>) -> Option<u32> {
+///     // `Lookup::Missing` and `None` both become `None`
+///     a? + b?
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn function(args: TokenStream, input: TokenStream) -> TokenStream {
+    let trait_path = quote! {::propagate::};
+    if let Some(arg) = TokenStream2::from(args).into_iter().next() {
+        return Error::new(arg.span(), "`#[propagate::function]` takes no arguments")
+            .into_compile_error()
+            .into();
+    }
+    let item = parse_macro_input!(input as syn::ItemFn);
+    function::rewrite_function(item, &trait_path).into()
+}
+
+/// Not public API. Parses the clauses of `good!`, `bad!`, `take!` and the `reject!` family.
+///
+/// Invoked as `__propagation!($crate good|bad|take|reject <expr> <clause>)`, so grammar mistakes are
+/// reported on the offending token instead of as a type error in the expansion.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __propagation(input: TokenStream) -> TokenStream {
+    let propagation = parse_macro_input!(input as propagation::Propagation);
+    propagation.into_token_stream().into()
+}
+
+/// Not public API. Rewrites the body of `attempt!`.
+///
+/// Invoked as `__attempt!($crate <statements>)`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __attempt(input: TokenStream) -> TokenStream {
+    let attempt = parse_macro_input!(input as attempt::Attempt);
+    attempt.into_token_stream().into()
+}
+
+/// Not public API. Expands `retry!`.
+///
+/// Invoked as `__retry!($crate <expr>; <options>; <clause>)`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __retry(input: TokenStream) -> TokenStream {
+    let retry = parse_macro_input!(input as retry::Retry);
+    retry.into_token_stream().into()
+}
+
+/// Not public API. Expands `first_good!`.
+///
+/// Invoked as `__first_good!($crate <expr>, <expr>, ...; <clause>)`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __first_good(input: TokenStream) -> TokenStream {
+    let first_good = parse_macro_input!(input as first_good::FirstGood);
+    first_good.into_token_stream().into()
+}
+
+/// Not public API. Expands `match_good!`.
+///
+/// Invoked as `__match_good!($crate <expr> { <arms> })`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __match_good(input: TokenStream) -> TokenStream {
+    let match_good = parse_macro_input!(input as match_good::MatchGood);
+    match_good.into_token_stream().into()
+}
+
+/// Not public API. Expands `for_good!`.
+///
+/// Invoked as `__for_good!($crate <pat> in <expr> <clause> { <body> })`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __for_good(input: TokenStream) -> TokenStream {
+    let for_good = parse_macro_input!(input as looping::ForGood);
+    for_good.into_token_stream().into()
+}
+
+/// Not public API. Expands `while_good!`.
+///
+/// Invoked as `__while_good!($crate let <pat> = <expr> { <body> })`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __while_good(input: TokenStream) -> TokenStream {
+    let while_good = parse_macro_input!(input as looping::WhileGood);
+    while_good.into_token_stream().into()
+}
@@ -0,0 +1,141 @@
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::{Expr, Ident, Lifetime, Pat, Token};
+
+use crate::helper_fn::split_body;
+use crate::propagation::Propagation;
+
+/// `for_good!('label: <pat> in <expr> <clause> { <body> })`
+pub struct ForGood {
+    label: Option<Lifetime>,
+    pat: Pat,
+    iter: Expr,
+    item: Ident,
+    propagation: Propagation,
+    breaks_with_value: bool,
+    body: TokenTree,
+}
+
+/// `while_good!(let <pat> = <expr> { <body> })`
+pub struct WhileGood {
+    krate: TokenTree,
+    label: Option<Lifetime>,
+    pat: Pat,
+    expr: Expr,
+    body: TokenTree,
+}
+
+fn parse_label(input: ParseStream) -> syn::Result<Option<Lifetime>> {
+    if !input.peek(Lifetime) {
+        return Ok(None);
+    }
+    let label = input.parse()?;
+    input.parse::<Token![:]>()?;
+    Ok(Some(label))
+}
+
+impl Parse for ForGood {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (head, body) = split_body(input.parse()?)?;
+        let parser = |input: ParseStream| {
+            // `$crate` from the calling `macro_rules!`
+            let krate: TokenTree = input.parse()?;
+            let label = parse_label(input)?;
+            let pat = Pat::parse_multi_with_leading_vert(input)?;
+            input.parse::<Token![in]>()?;
+            let iter: Expr = input.parse()?;
+            let item = Ident::new("__item", Span::mixed_site());
+            let scrutinee = Expr::Verbatim(item.to_token_stream());
+            let (propagation, breaks_with_value) =
+                Propagation::parse_loop_item(krate, scrutinee, input)?;
+            Ok(ForGood {
+                label,
+                pat,
+                iter,
+                item,
+                propagation,
+                breaks_with_value,
+                body,
+            })
+        };
+        parser.parse2(head)
+    }
+}
+
+impl ToTokens for ForGood {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let ForGood {
+            label,
+            pat,
+            iter,
+            item,
+            propagation,
+            breaks_with_value,
+            body,
+        } = self;
+        let iter_ident = Ident::new("__iter", Span::mixed_site());
+        let label = label.as_ref().map(|label| quote! { #label: });
+        let exhausted = match breaks_with_value {
+            true => quote! { ::core::option::Option::None },
+            false => quote! {},
+        };
+        tokens.extend(quote! {{
+            let mut #iter_ident = ::core::iter::IntoIterator::into_iter(#iter);
+            #label loop {
+                let #item = match ::core::iter::Iterator::next(&mut #iter_ident) {
+                    ::core::option::Option::Some(#item) => #item,
+                    ::core::option::Option::None => break #exhausted,
+                };
+                let #pat = #propagation;
+                #body
+            }
+        }});
+    }
+}
+
+impl Parse for WhileGood {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (head, body) = split_body(input.parse()?)?;
+        let parser = |input: ParseStream| {
+            // `$crate` from the calling `macro_rules!`
+            let krate: TokenTree = input.parse()?;
+            let label = parse_label(input)?;
+            input.parse::<Token![let]>()?;
+            let pat = Pat::parse_multi_with_leading_vert(input)?;
+            input.parse::<Token![=]>()?;
+            let expr: Expr = input.parse()?;
+            Ok(WhileGood {
+                krate,
+                label,
+                pat,
+                expr,
+                body,
+            })
+        };
+        parser.parse2(head)
+    }
+}
+
+impl ToTokens for WhileGood {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let WhileGood {
+            krate,
+            label,
+            pat,
+            expr,
+            body,
+        } = self;
+        let label_def = label.as_ref().map(|label| quote! { #label: });
+        let value = Ident::new("v", Span::mixed_site());
+        tokens.extend(quote! {
+            #label_def loop {
+                let #pat = match #krate::TwoStates::two_states(#expr) {
+                    ::core::result::Result::Ok(#value) => #value,
+                    ::core::result::Result::Err(#value) => break #label #value,
+                };
+                #body
+            }
+        });
+    }
+}
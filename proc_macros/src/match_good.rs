@@ -0,0 +1,217 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::{parenthesized, Error, Expr, Ident, Pat, Token, Type};
+
+use crate::helper_fn::split_body;
+
+#[derive(Clone, Copy, PartialEq)]
+enum ArmKind {
+    Good,
+    Bad,
+    Rest,
+}
+
+/// `good(<pat>[: <type>]) => <body>`
+struct Arm {
+    kind: ArmKind,
+    keyword: Ident,
+    pat: Pat,
+    ty: Option<Type>,
+    body: Expr,
+}
+
+/// `match_good!(<expr> { <arms> })`
+pub struct MatchGood {
+    krate: TokenTree,
+    expr: Expr,
+    arms: Vec<Arm>,
+}
+
+/// Whether a `,` may be omitted after the arm body, like in a `match`
+fn is_block_like(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Block(_)
+            | Expr::If(_)
+            | Expr::Match(_)
+            | Expr::Loop(_)
+            | Expr::While(_)
+            | Expr::ForLoop(_)
+            | Expr::Unsafe(_)
+            | Expr::Const(_)
+    )
+}
+
+impl Parse for Arm {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let keyword: Ident = input.parse()?;
+        let kind = match keyword.to_string().as_str() {
+            "good" => ArmKind::Good,
+            "bad" => ArmKind::Bad,
+            "rest" => ArmKind::Rest,
+            _ => {
+                return Err(Error::new(
+                    keyword.span(),
+                    "expected `good(..)`, `bad(..)` or `rest(..)`",
+                ))
+            }
+        };
+        let content;
+        parenthesized!(content in input);
+        let pat = Pat::parse_single(&content)?;
+        let ty = match content.peek(Token![:]) {
+            true => {
+                content.parse::<Token![:]>()?;
+                Some(content.parse()?)
+            }
+            false => None,
+        };
+        if !content.is_empty() {
+            return Err(content.error("expected a pattern, optionally followed by `: <type>`"));
+        }
+        input.parse::<Token![=>]>()?;
+        let body: Expr = input.parse()?;
+        if !input.is_empty() {
+            if is_block_like(&body) {
+                let _ = input.parse::<Option<Token![,]>>()?;
+            } else {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(Arm {
+            kind,
+            keyword,
+            pat,
+            ty,
+            body,
+        })
+    }
+}
+
+impl Parse for MatchGood {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (head, body) = split_body(input.parse()?)?;
+        let (krate, expr) = (|input: ParseStream| {
+            // `$crate` from the calling `macro_rules!`
+            let krate: TokenTree = input.parse()?;
+            let expr: Expr = input.parse()?;
+            Ok((krate, expr))
+        })
+        .parse2(head)?;
+        let TokenTree::Group(body) = body else {
+            unreachable!("`split_body` gives a group")
+        };
+        let arms = (|input: ParseStream| {
+            let mut arms: Vec<Arm> = Vec::new();
+            while !input.is_empty() {
+                arms.push(input.parse()?);
+            }
+            Ok(arms)
+        })
+        .parse2(body.stream())?;
+
+        let rest_arms: Vec<&Arm> = arms
+            .iter()
+            .filter(|arm| arm.kind == ArmKind::Rest)
+            .collect();
+        if let Some(extra) = rest_arms.get(1) {
+            return Err(Error::new(
+                extra.keyword.span(),
+                "only one `rest(..)` arm is allowed",
+            ));
+        }
+        if let Some(rest) = rest_arms.first() {
+            if !matches!(arms.last(), Some(last) if last.kind == ArmKind::Rest) {
+                return Err(Error::new(
+                    rest.keyword.span(),
+                    "the `rest(..)` arm must be the last arm",
+                ));
+            }
+        } else {
+            let count = |kind| arms.iter().filter(|arm| arm.kind == kind).count();
+            if count(ArmKind::Good) != 1 || count(ArmKind::Bad) != 1 {
+                return Err(Error::new(
+                    body.span_close(),
+                    "expected a `rest(..)` arm\n\
+                    help: only one `good(..)` and one `bad(..)` arm on a `TwoStates` enum are \
+                    exhaustive without it",
+                ));
+            }
+        }
+        Ok(MatchGood { krate, expr, arms })
+    }
+}
+
+impl ToTokens for MatchGood {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let MatchGood { krate, expr, arms } = self;
+        let value = Ident::new("__value", Span::mixed_site());
+        let rest = arms.iter().find(|arm| arm.kind == ArmKind::Rest);
+        let Some(rest) = rest else {
+            // One good and one bad arm, exhaustive through `TwoStates`
+            let good = arms.iter().find(|arm| arm.kind == ArmKind::Good).unwrap();
+            let bad = arms.iter().find(|arm| arm.kind == ArmKind::Bad).unwrap();
+            let infer = quote! { _ };
+            let good_ty = good
+                .ty
+                .as_ref()
+                .map_or(infer.clone(), ToTokens::to_token_stream);
+            let bad_ty = bad.ty.as_ref().map_or(infer, ToTokens::to_token_stream);
+            let (good_pat, good_body, bad_pat, bad_body) =
+                (&good.pat, &good.body, &bad.pat, &bad.body);
+            tokens.extend(quote! {
+                match #krate::TwoStates::<#good_ty, #bad_ty>::two_states(#expr) {
+                    ::core::result::Result::Ok(#good_pat) => #good_body,
+                    ::core::result::Result::Err(#bad_pat) => #bad_body,
+                }
+            });
+            return;
+        };
+
+        // Try each arm in order, the value falls through to the next one
+        let (rest_pat, rest_body) = (&rest.pat, &rest.body);
+        let mut dispatch = quote! {{
+            let #rest_pat = #value;
+            #rest_body
+        }};
+        for arm in arms.iter().rev().filter(|arm| arm.kind != ArmKind::Rest) {
+            let Arm { pat, ty, body, .. } = arm;
+            let ty = ty.as_ref().map(|ty| quote! { ::<#ty> });
+            dispatch = match arm.kind {
+                ArmKind::Good => quote! {
+                    match #krate::Good #ty::good(#value) {
+                        ::core::result::Result::Ok(#pat) => #body,
+                        ::core::result::Result::Err(#value) => #dispatch,
+                    }
+                },
+                _ => quote! {
+                    match #krate::Bad #ty::bad(#value) {
+                        ::core::result::Result::Err(#pat) => #body,
+                        ::core::result::Result::Ok(#value) => #dispatch,
+                    }
+                },
+            };
+        }
+        // After a `good` and a `bad` arm, nothing is left of a two-state enum for the `rest` arm
+        let has_arm = |kind| arms.iter().any(|arm| arm.kind == kind);
+        let check = (has_arm(ArmKind::Good) && has_arm(ArmKind::Bad)).then(|| {
+            quote_spanned! {rest.keyword.span()=>
+                {
+                    #[allow(unused_imports)]
+                    use #krate::__private::{RestKindOther as _, RestKindTwoStates as _};
+                    #krate::__private::rest_arm_is_reachable(
+                        (&#krate::__private::KindOf::of(&#value)).__rest_kind(),
+                    );
+                }
+            }
+        });
+        tokens.extend(quote! {{
+            let #value = #expr;
+            #check
+            #dispatch
+        }});
+    }
+}
@@ -0,0 +1,727 @@
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Expr, ExprCast, Ident, Lifetime, Token, Type};
+
+use crate::helper_fn::display_type;
+
+/// Which variant the macro keeps, everything else is propagated
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    /// `good!`, keeps the good value
+    Good,
+    /// `bad!`, keeps the bad value
+    Bad,
+    /// `take!`, the input is already a `Result` of the taken value and the whole enum
+    Take,
+    /// `reject!`, `reject_good!` and `reject_bad!`, the input is already a `Result` of the whole
+    /// enum and the rejected value
+    Reject,
+}
+
+impl Mode {
+    /// `take!` and the `reject!` family pick the variant themselves, so there are no two states
+    fn picks_variant(self) -> bool {
+        matches!(self, Mode::Take | Mode::Reject)
+    }
+}
+
+/// What happens to the propagated value
+enum Clause {
+    /// `good!(x)`: return the whole enum
+    Whole,
+    /// `good!(x; expect "...")` and friends, reporting the bad value of a two-state enum and
+    /// the whole enum otherwise
+    SemicolonPanic(PanicClause),
+    /// `good!(x; ...)`: handed to `__propagate!` as is
+    Semicolon(TokenStream2),
+    /// `good!(x; recover)`: the good value is taken back out of the error through `Recover`
+    Recover,
+    /// `good!(x => ...)` or `good!(x => full ...)`
+    Arrow { full: bool, body: ArrowBody },
+    /// A bare `good!(x)` rewritten by `attempt!`, breaks out of its block
+    Attempt { label: Lifetime },
+}
+
+enum ArrowBody {
+    /// `=> do <closure>; ...`
+    Do { closure: Expr, then: TokenStream2 },
+    /// `=> break 'label <closure>`
+    Break {
+        label: Option<Lifetime>,
+        closure: Expr,
+    },
+    /// `=> else <closure>`
+    Else { closure: Expr },
+    /// `=> _`
+    Transparent,
+    /// `=> boxed`, returns the bad value as a `Box<dyn Error + Send + Sync>` through `FromBad`
+    Boxed,
+    /// `=> inspect <closure>; ...`, the closure gets a reference, then the value is propagated
+    /// with the clause after `;`, or returned when there is none
+    Inspect {
+        closure: Expr,
+        then: Option<TokenStream2>,
+    },
+    /// `=> <closure>`
+    Return { closure: Expr },
+    /// `=> expect "..."` and friends
+    Panic(PanicClause),
+}
+
+/// Panics with the propagated value, `Debug` formatted
+enum PanicClause {
+    /// `expect <message>`
+    Expect { message: Expr },
+    /// `unreachable`
+    Unreachable,
+    /// `todo`
+    Todo,
+    /// `debug_expect <message>; ...`, propagates with the clause after `;` in release builds
+    DebugExpect { message: Expr, then: TokenStream2 },
+}
+
+pub struct Propagation {
+    krate: TokenTree,
+    mode: Mode,
+    scrutinee: Expr,
+    /// `good!(x as T)`, picks the `Good<T>` or `Bad<T>` impl of overloaded variants
+    payload: Option<Type>,
+    clause: Clause,
+}
+
+/// The keywords after `=>`, listed in errors
+const ARROW_KEYWORD_LIST: &str = "`do`, `break`, `else`, `full`, `inspect`, `boxed`, `expect`, \
+    `debug_expect`, `unreachable`, `todo`, `_`";
+
+const ARROW_KEYWORDS: &[&str] = &[
+    "boxed",
+    "inspect",
+    "do",
+    "break",
+    "else",
+    "full",
+    "continue",
+    "expect",
+    "debug_expect",
+];
+
+impl Parse for Propagation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // `$crate` from the calling `macro_rules!`
+        let krate: TokenTree = input.parse()?;
+        let mode: Ident = input.parse()?;
+        let mode = match mode.to_string().as_str() {
+            "good" => Mode::Good,
+            "bad" => Mode::Bad,
+            "take" => Mode::Take,
+            "reject" => Mode::Reject,
+            _ => return Err(Error::new(mode.span(), "unknown propagation mode")),
+        };
+        if input.is_empty() {
+            return Err(input.error("expected an expression to propagate"));
+        }
+        // `@attempt 'label <expr>`, written by `attempt!`
+        let attempt = match input.peek(Token![@]) && !mode.picks_variant() {
+            true => {
+                input.parse::<Token![@]>()?;
+                let keyword: Ident = input.parse()?;
+                if keyword != "attempt" {
+                    return Err(Error::new(keyword.span(), "expected `attempt`"));
+                }
+                Some(input.parse::<Lifetime>()?)
+            }
+            false => None,
+        };
+        let mut scrutinee: Expr = input.parse()?;
+        // A cast of the whole expression names the payload type, `good!((x as T))` still casts
+        let mut payload = None;
+        if !mode.picks_variant() {
+            if let Expr::Cast(ExprCast { expr, ty, .. }) = scrutinee {
+                scrutinee = *expr;
+                payload = Some(payload_type(*ty, mode)?);
+            }
+        }
+        let clause = match attempt {
+            Some(label) if input.is_empty() => Clause::Attempt { label },
+            Some(_) => return Err(input.error("expected the end of the expression")),
+            None => parse_clause(input, mode)?,
+        };
+        Ok(Propagation {
+            krate,
+            mode,
+            scrutinee,
+            payload,
+            clause,
+        })
+    }
+}
+
+/// The payload type after `as`. A primitive reads like a numeric or pointer cast, so it has to
+/// be parenthesized, `good!(x as (u32))`, to tell the two apart.
+fn payload_type(ty: Type, mode: Mode) -> syn::Result<Type> {
+    let primitive = match &ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .get_ident()
+            .is_some_and(|ident| PRIMITIVE_TYPES.iter().any(|primitive| ident == primitive)),
+        Type::Ptr(_) => true,
+        _ => false,
+    };
+    if primitive {
+        let name = display_type(&ty);
+        let macro_name = match mode {
+            Mode::Bad => "bad",
+            _ => "good",
+        };
+        return Err(Error::new_spanned(
+            &ty,
+            format!(
+                "`as {name}` is ambiguous between a cast and the payload type\n\
+                help: wrap the cast in parentheses to cast, like `{macro_name}!((x as {name}))`, \
+                or write `as ({name})` to pick the payload type"
+            ),
+        ));
+    }
+    match ty {
+        Type::Paren(paren) => Ok(*paren.elem),
+        ty => Ok(ty),
+    }
+}
+
+const PRIMITIVE_TYPES: &[&str] = &[
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize", "f32", "f64",
+];
+
+impl Propagation {
+    /// `good!` on a value computed by another macro, followed by the usual clauses
+    pub fn parse_good(krate: TokenTree, scrutinee: Expr, input: ParseStream) -> syn::Result<Self> {
+        let clause = parse_clause(input, Mode::Good)?;
+        Ok(Propagation {
+            krate,
+            mode: Mode::Good,
+            scrutinee,
+            payload: None,
+            clause,
+        })
+    }
+
+    /// `take!` on a `Result` of the kept value and everything else, built by another macro
+    pub fn parse_take(krate: TokenTree, scrutinee: Expr, input: ParseStream) -> syn::Result<Self> {
+        let clause = parse_clause(input, Mode::Take)?;
+        Ok(Propagation {
+            krate,
+            mode: Mode::Take,
+            scrutinee,
+            payload: None,
+            clause,
+        })
+    }
+
+    /// `good!` on each item of a `for_good!` loop. An omitted clause continues the loop, and an
+    /// unlabelled `break` with a value breaks with `Some(value)`, returns whether any does.
+    pub fn parse_loop_item(
+        krate: TokenTree,
+        scrutinee: Expr,
+        input: ParseStream,
+    ) -> syn::Result<(Self, bool)> {
+        let mut clause = match input.is_empty() {
+            true => Clause::Semicolon(TokenStream2::new()),
+            false => parse_clause(input, Mode::Good)?,
+        };
+        let breaks_with_value = match &mut clause {
+            Clause::Arrow {
+                body: ArrowBody::Inspect { then, .. },
+                ..
+            } => loop_then(then.get_or_insert_with(TokenStream2::new)),
+            Clause::Semicolon(then)
+            | Clause::SemicolonPanic(PanicClause::DebugExpect { then, .. })
+            | Clause::Arrow {
+                body:
+                    ArrowBody::Do { then, .. } | ArrowBody::Panic(PanicClause::DebugExpect { then, .. }),
+                ..
+            } => loop_then(then),
+            Clause::Arrow {
+                body:
+                    ArrowBody::Break {
+                        label: None,
+                        closure,
+                    },
+                ..
+            } => {
+                let value = Ident::new("v", Span::mixed_site());
+                *closure = Expr::Verbatim(quote! {
+                    |#value| ::core::option::Option::Some((#closure)(#value))
+                });
+                true
+            }
+            _ => false,
+        };
+        let propagation = Propagation {
+            krate,
+            mode: Mode::Good,
+            scrutinee,
+            payload: None,
+            clause,
+        };
+        Ok((propagation, breaks_with_value))
+    }
+}
+
+/// Continues the loop when nothing follows, and wraps the value of an unlabelled `break` in `Some`
+fn loop_then(then: &mut TokenStream2) -> bool {
+    let mut tokens = then.clone().into_iter().peekable();
+    match tokens.next() {
+        None => {
+            *then = quote! { continue };
+            false
+        }
+        Some(TokenTree::Ident(keyword)) if keyword == "break" => {
+            let labelled =
+                matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '\'');
+            let value: TokenStream2 = tokens.collect();
+            if labelled || value.is_empty() {
+                return false;
+            }
+            *then = quote! { #keyword ::core::option::Option::Some(#value) };
+            true
+        }
+        Some(_) => false,
+    }
+}
+
+fn parse_clause(input: ParseStream, mode: Mode) -> syn::Result<Clause> {
+    if input.is_empty() {
+        return Ok(Clause::Whole);
+    }
+    if input.peek(Token![;]) {
+        input.parse::<Token![;]>()?;
+        if let Some(panic) = parse_panic_clause(input)? {
+            return Ok(Clause::SemicolonPanic(panic));
+        }
+        // Only a keyword when nothing follows, so `; recover(e)` keeps its meaning
+        if matches!(input.cursor().ident(), Some((ident, rest)) if ident == "recover" && rest.eof()) {
+            let recover: Ident = input.parse()?;
+            if mode != Mode::Good {
+                return Err(Error::new(
+                    recover.span(),
+                    "`recover` gets the good value back out of the error, it only works with `good!`",
+                ));
+            }
+            return Ok(Clause::Recover);
+        }
+        return Ok(Clause::Semicolon(input.parse()?));
+    }
+    if input.peek(Token![=>]) {
+        input.parse::<Token![=>]>()?;
+        let full = peek_keyword(input, "full");
+        if full {
+            let full: Ident = input.parse()?;
+            match mode {
+                Mode::Take => {
+                    return Err(Error::new(
+                        full.span(),
+                        "`full` can be omitted here because `take!` should be used on non-`TwoStates` enums, \
+                        we can never infer the other inner value",
+                    ))
+                }
+                Mode::Reject => {
+                    return Err(Error::new(
+                        full.span(),
+                        "`full` can be omitted here because the rejected value is always propagated as is",
+                    ))
+                }
+                Mode::Good | Mode::Bad => {}
+            }
+        }
+        let body = parse_arrow_body(input)?;
+        return Ok(Clause::Arrow { full, body });
+    }
+    let found = input.cursor().token_tree().map(|(tt, _)| tt);
+    let mut msg = format!(
+        "expected `;` or `=>` after the expression, found `{}`",
+        found.as_ref().map(ToString::to_string).unwrap_or_default()
+    );
+    if input.peek(Token![,]) {
+        msg.push_str(
+            "\nhelp: use `;` to propagate with a value or control flow, like `; continue`",
+        );
+    } else {
+        msg.push_str(
+            "\nhelp: use `; <expr>` to propagate with a value or control flow, \
+            or `=> <closure>` to apply a closure",
+        );
+    }
+    Err(input.error(msg))
+}
+
+fn parse_arrow_body(input: ParseStream) -> syn::Result<ArrowBody> {
+    if input.is_empty() {
+        return Err(input.error(format!(
+            "expected a closure or one of {ARROW_KEYWORD_LIST} after `=>`"
+        )));
+    }
+    if input.peek(Token![do]) {
+        input.parse::<Token![do]>()?;
+        let closure: Expr = input.parse()?;
+        if !input.peek(Token![;]) {
+            let msg = if input.is_empty() {
+                "expected `;` after the `do` closure\n\
+                help: end it with `;` to return, or add a clause like `; continue`"
+            } else {
+                "expected `;` after the `do` closure\n\
+                help: separate the closure from the clause, like `do |v| ...; continue`"
+            };
+            return Err(input.error(msg));
+        }
+        input.parse::<Token![;]>()?;
+        let then: TokenStream2 = input.parse()?;
+        return Ok(ArrowBody::Do { closure, then });
+    }
+    if input.peek(Token![continue]) {
+        return Err(input.error(
+            "This syntax implies \"continue with value, applying closure\", but `continue` cannot return any value.\n\
+            So use `<your_enum> => do <your_closure>; continue` instead",
+        ));
+    }
+    if input.peek(Token![break]) {
+        input.parse::<Token![break]>()?;
+        let label: Option<Lifetime> = if input.peek(Lifetime) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        if input.is_empty() {
+            return Err(input.error(
+                "expected a closure after `break`\n\
+                help: use `; break` to break without a value",
+            ));
+        }
+        let closure: Expr = input.parse()?;
+        ensure_end(input, "the `break` closure")?;
+        return Ok(ArrowBody::Break { label, closure });
+    }
+    if input.peek(Token![else]) {
+        input.parse::<Token![else]>()?;
+        let closure: Expr = input.parse()?;
+        ensure_end(input, "the `else` closure")?;
+        return Ok(ArrowBody::Else { closure });
+    }
+    if input.peek(Token![_]) {
+        input.parse::<Token![_]>()?;
+        ensure_end(input, "`_`")?;
+        return Ok(ArrowBody::Transparent);
+    }
+    if let Some(panic) = parse_panic_clause(input)? {
+        return Ok(ArrowBody::Panic(panic));
+    }
+    // Only a keyword when nothing follows, so `=> boxed(...)` keeps its meaning
+    if matches!(input.cursor().ident(), Some((ident, rest)) if ident == "boxed" && rest.eof()) {
+        input.parse::<Ident>()?;
+        return Ok(ArrowBody::Boxed);
+    }
+    // Only a keyword when a closure or a function follows, so `=> inspect` keeps its meaning
+    let inspect = input.cursor().ident().is_some_and(|(ident, rest)| {
+        ident == "inspect"
+            && (rest.ident().is_some() || matches!(rest.punct(), Some((p, _)) if p.as_char() == '|'))
+    });
+    if inspect {
+        input.parse::<Ident>()?;
+        let closure: Expr = input.parse()?;
+        let then = match input.peek(Token![;]) {
+            true => {
+                input.parse::<Token![;]>()?;
+                Some(input.parse()?)
+            }
+            false => {
+                ensure_end(input, "the `inspect` closure")?;
+                None
+            }
+        };
+        return Ok(ArrowBody::Inspect { closure, then });
+    }
+    check_misspelled_keyword(input)?;
+    let closure: Expr = input.parse()?;
+    ensure_end(input, "the closure")?;
+    Ok(ArrowBody::Return { closure })
+}
+
+/// `expect` and `debug_expect` are only keywords when a message follows, and `unreachable` and
+/// `todo` when nothing does, so `; unreachable!()` or `; expect(value)` keep their meaning
+fn parse_panic_clause(input: ParseStream) -> syn::Result<Option<PanicClause>> {
+    let (ident, rest) = match input.cursor().ident() {
+        Some(ident) => ident,
+        None => return Ok(None),
+    };
+    let followed_by_message = matches!(
+        rest.token_tree(),
+        Some((TokenTree::Literal(_), _)) | Some((TokenTree::Ident(_), _))
+    );
+    let clause = match ident.to_string().as_str() {
+        "expect" if followed_by_message => {
+            input.parse::<Ident>()?;
+            let message: Expr = input.parse()?;
+            ensure_end(input, "the `expect` message")?;
+            PanicClause::Expect { message }
+        }
+        "debug_expect" if followed_by_message => {
+            input.parse::<Ident>()?;
+            let message: Expr = input.parse()?;
+            if !input.peek(Token![;]) {
+                return Err(input.error(
+                    "expected `;` after the `debug_expect` message\n\
+                    help: add the clause used in release builds, like `debug_expect \"...\"; continue`",
+                ));
+            }
+            input.parse::<Token![;]>()?;
+            let then: TokenStream2 = input.parse()?;
+            PanicClause::DebugExpect { message, then }
+        }
+        "unreachable" if rest.eof() => {
+            input.parse::<Ident>()?;
+            PanicClause::Unreachable
+        }
+        "todo" if rest.eof() => {
+            input.parse::<Ident>()?;
+            PanicClause::Todo
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(clause))
+}
+
+fn peek_keyword(input: ParseStream, keyword: &str) -> bool {
+    input
+        .cursor()
+        .ident()
+        .is_some_and(|(ident, _)| ident == keyword)
+}
+
+fn ensure_end(input: ParseStream, after: &str) -> syn::Result<()> {
+    if input.is_empty() {
+        Ok(())
+    } else {
+        Err(input.error(format!(
+            "unexpected tokens after {after}\n\
+            help: use `=> do <closure>; <clause>` to run a closure before `continue`, `break` or `return`"
+        )))
+    }
+}
+
+/// An identifier followed by a closure or a literal is most likely a misspelled keyword,
+/// like `=> contine |e| e`, which would otherwise parse as `contine | e | e`
+fn check_misspelled_keyword(input: ParseStream) -> syn::Result<()> {
+    let (ident, rest) = match input.cursor().ident() {
+        Some(ident) => ident,
+        None => return Ok(()),
+    };
+    let followed_by_argument = match rest.token_tree() {
+        Some((TokenTree::Punct(punct), _)) => punct.as_char() == '|',
+        Some((TokenTree::Ident(next), _)) => next == "move",
+        Some((TokenTree::Literal(_), _)) => true,
+        Some((TokenTree::Group(_), _)) | None => false,
+    } || rest.lifetime().is_some();
+    if !followed_by_argument {
+        return Ok(());
+    }
+    let name = ident.to_string();
+    let suggestion = ARROW_KEYWORDS
+        .iter()
+        .map(|keyword| (edit_distance(&name, keyword), keyword))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, keyword)| *keyword);
+    let mut msg =
+        format!("unknown keyword `{name}`, expected a closure or one of {ARROW_KEYWORD_LIST}");
+    match suggestion {
+        Some("continue") => msg.push_str("\nhelp: did you mean `do <closure>; continue`?"),
+        Some(keyword) => msg.push_str(&format!("\nhelp: did you mean `{keyword}`?")),
+        None => return Ok(()),
+    }
+    Err(Error::new(ident.span(), msg))
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = Vec::with_capacity(b.len() + 1);
+        current.push(i + 1);
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + (a != *b) as usize;
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+impl ToTokens for Propagation {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Propagation {
+            krate,
+            mode,
+            scrutinee,
+            payload,
+            clause,
+        } = self;
+        // `good!((x as T))` would be a parenthesized function argument, and rustc would suggest
+        // removing the parentheses, which names the payload type instead of casting
+        let scrutinee = match scrutinee {
+            Expr::Paren(paren) if !mode.picks_variant() => &*paren.expr,
+            scrutinee => scrutinee,
+        };
+        // `=> inspect` keeps the whole enum to return it, and only borrows the bad value
+        let inspect = matches!(
+            clause,
+            Clause::Arrow {
+                full: false,
+                body: ArrowBody::Inspect { .. },
+            }
+        ) && !mode.picks_variant();
+        let two_states = matches!(
+            clause,
+            Clause::Arrow { full: false, .. } | Clause::Attempt { .. }
+        ) && !mode.picks_variant()
+            && !inspect;
+        let source = match (mode, two_states, payload) {
+            (Mode::Take | Mode::Reject, _, _) => quote! { #scrutinee },
+            (_, true, None) => quote! { #krate::TwoStates::two_states(#scrutinee) },
+            (Mode::Good, true, Some(ty)) => {
+                quote! { #krate::TwoStates::<#ty, _>::two_states(#scrutinee) }
+            }
+            (Mode::Bad, true, Some(ty)) => {
+                quote! { #krate::TwoStates::<_, #ty>::two_states(#scrutinee) }
+            }
+            (Mode::Good, false, None) => quote! { #krate::Good::good(#scrutinee) },
+            (Mode::Good, false, Some(ty)) => quote! { #krate::Good::<#ty>::good(#scrutinee) },
+            (Mode::Bad, false, None) => quote! { #krate::Bad::bad(#scrutinee) },
+            (Mode::Bad, false, Some(ty)) => quote! { #krate::Bad::<#ty>::bad(#scrutinee) },
+        };
+        let ok = quote! { ::core::result::Result::Ok };
+        let err = quote! { ::core::result::Result::Err };
+        let (keep, dump) = match mode {
+            Mode::Good | Mode::Take | Mode::Reject => (ok, err),
+            Mode::Bad => (err, ok),
+        };
+        let value = Ident::new("v", Span::mixed_site());
+        let enum_ = Ident::new("__enum", Span::mixed_site());
+        let dump_arm = match clause {
+            Clause::Whole => quote! {
+                #dump(#enum_) => return #enum_,
+            },
+            Clause::SemicolonPanic(panic) => {
+                let method = match mode {
+                    Mode::Good => Some(quote! { bad_value }),
+                    Mode::Bad => Some(quote! { good_value }),
+                    Mode::Take | Mode::Reject => None,
+                };
+                let value = match method {
+                    Some(method) => quote! {{
+                        #[allow(unused_imports)]
+                        use #krate::__private::{DumpTwoStates as _, DumpWhole as _};
+                        (&#krate::__private::KindOf::of(&#enum_)).__dump_kind().#method(#enum_)
+                    }},
+                    None => enum_.to_token_stream(),
+                };
+                let panic = panic_tokens(krate, panic, &value);
+                quote! {
+                    #[allow(unreachable_code)]
+                    #[allow(clippy::diverging_sub_expression)]
+                    #dump(#enum_) => #panic,
+                }
+            }
+            Clause::Semicolon(propagate) => quote! {
+                #[allow(unreachable_code)]
+                #[allow(clippy::diverging_sub_expression)]
+                #dump(_) => #krate::__propagate!(#propagate),
+            },
+            Clause::Recover => quote! {
+                #dump(#enum_) => #krate::Recover::recover(#enum_),
+            },
+            Clause::Arrow { body, .. } => {
+                let propagate = match body {
+                    ArrowBody::Do { closure, then } => quote! {{
+                        (#closure)(#enum_);
+                        #krate::__propagate!(#then)
+                    }},
+                    ArrowBody::Break { label, closure } => {
+                        quote! { break #label (#closure)(#enum_) }
+                    }
+                    ArrowBody::Else { closure } => quote! { (#closure)(#enum_) },
+                    ArrowBody::Transparent => quote! { return #enum_ },
+                    ArrowBody::Boxed => quote! {{
+                        #[allow(unused_imports)]
+                        use #krate::__private::{BoxDisplay as _, BoxError as _};
+                        return #krate::FromBad::from_bad((&#enum_).__box_kind().boxed(#enum_))
+                    }},
+                    ArrowBody::Return { closure } => quote! { return (#closure)(#enum_) },
+                    ArrowBody::Inspect { closure, then } => {
+                        let propagate = match then {
+                            Some(then) => quote! { #krate::__propagate!(#then) },
+                            None => quote! { return #enum_ },
+                        };
+                        // `&Result` and `&mut Result` scrutinees are inspected through `&Result`
+                        let borrowed = quote! {{
+                            #[allow(unused_imports)]
+                            use #krate::__private::{BorrowOwned as _, BorrowReference as _};
+                            (&#krate::__private::KindOf::of(&#enum_)).__borrow_kind().borrow(&#enum_)
+                        }};
+                        let observed = match (inspect, mode) {
+                            (true, Mode::Bad) => quote! { #krate::__private::inspect_good(#borrowed) },
+                            (true, _) => quote! { #krate::__private::inspect_bad(#borrowed) },
+                            (false, _) => quote! { &#enum_ },
+                        };
+                        quote! {{
+                            (#closure)(#observed);
+                            #propagate
+                        }}
+                    }
+                    ArrowBody::Panic(panic) => {
+                        panic_tokens(krate, panic, &enum_.to_token_stream())
+                    }
+                };
+                quote! {
+                    #[allow(unreachable_code)]
+                    #[allow(clippy::diverging_sub_expression)]
+                    #dump(#enum_) => #propagate,
+                }
+            }
+            Clause::Attempt { label } => {
+                let from = match mode {
+                    Mode::Bad => quote! { FromGood::from_good },
+                    Mode::Good | Mode::Take | Mode::Reject => quote! { FromBad::from_bad },
+                };
+                quote! {
+                    #dump(#enum_) => break #label #krate::#from(#enum_),
+                }
+            }
+        };
+        tokens.extend(quote! {
+            match #source {
+                #keep(#value) => #value,
+                #dump_arm
+            }
+        });
+    }
+}
+
+fn panic_tokens(krate: &TokenTree, panic: &PanicClause, value: &TokenStream2) -> TokenStream2 {
+    match panic {
+        PanicClause::Expect { message } => {
+            quote! { #krate::__private::expect_failed(#message, &#value) }
+        }
+        PanicClause::Unreachable => quote! { #krate::__private::unreachable_value(&#value) },
+        PanicClause::Todo => quote! { #krate::__private::todo_value(&#value) },
+        PanicClause::DebugExpect { message, then } => quote! {{
+            if ::core::cfg!(debug_assertions) {
+                #krate::__private::expect_failed(#message, &#value)
+            }
+            #krate::__propagate!(#then)
+        }},
+    }
+}
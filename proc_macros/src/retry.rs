@@ -0,0 +1,171 @@
+use alloc::format;
+use alloc::string::ToString;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Expr, ExprLit, Ident, Lit, Token};
+
+use crate::propagation::Propagation;
+
+const OPTIONS: &[&str] = &["max", "backoff", "when"];
+
+/// `retry!(<expr>; max = <n>, backoff = <closure>, when = <closure>; <clause>)`
+pub struct Retry {
+    krate: TokenTree,
+    expr: Expr,
+    max: Option<Expr>,
+    backoff: Option<Expr>,
+    when: Option<Expr>,
+    propagation: Propagation,
+}
+
+/// Options follow the first `;` as `<name> = <expr>`, anything else is a `good!` clause
+fn peek_options(input: ParseStream) -> bool {
+    let fork = input.fork();
+    fork.parse::<Token![;]>().is_ok()
+        && fork
+            .parse::<Ident>()
+            .is_ok_and(|ident| OPTIONS.iter().any(|option| ident == option))
+        && fork.peek(Token![=])
+        && !fork.peek(Token![==])
+}
+
+/// A literal `0`, maybe with a suffix like `0u32`
+fn is_zero(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_digits() == "0",
+        _ => false,
+    }
+}
+
+impl Parse for Retry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // `$crate` from the calling `macro_rules!`
+        let krate: TokenTree = input.parse()?;
+        if input.is_empty() {
+            return Err(input.error("expected an expression to retry"));
+        }
+        let expr: Expr = input.parse()?;
+        let (mut max, mut backoff, mut when) = (None, None, None);
+        if peek_options(input) {
+            input.parse::<Token![;]>()?;
+            loop {
+                let name: Ident = input.parse()?;
+                let slot = match name.to_string().as_str() {
+                    "max" => &mut max,
+                    "backoff" => &mut backoff,
+                    "when" => &mut when,
+                    _ => {
+                        return Err(Error::new(
+                            name.span(),
+                            "unknown option, expected `max`, `backoff` or `when`",
+                        ))
+                    }
+                };
+                if slot.is_some() {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("`{name}` is specified more than once"),
+                    ));
+                }
+                input.parse::<Token![=]>()?;
+                let value: Expr = input.parse()?;
+                if name == "max" && is_zero(&value) {
+                    return Err(Error::new_spanned(
+                        &value,
+                        "`max = 0` would never evaluate the expression, there is no value to propagate\n\
+                        help: use `max = 1` to evaluate it once without retrying",
+                    ));
+                }
+                *slot = Some(value);
+                if !input.peek(Token![,]) {
+                    break;
+                }
+                input.parse::<Token![,]>()?;
+            }
+        }
+        let value = Ident::new("__value", Span::mixed_site());
+        let scrutinee = Expr::Verbatim(value.into_token_stream());
+        let propagation = Propagation::parse_good(krate.clone(), scrutinee, input)?;
+        Ok(Retry {
+            krate,
+            expr,
+            max,
+            backoff,
+            when,
+            propagation,
+        })
+    }
+}
+
+impl ToTokens for Retry {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Retry {
+            krate,
+            expr,
+            max,
+            backoff,
+            when,
+            propagation,
+        } = self;
+        let value = Ident::new("__value", Span::mixed_site());
+        let attempts = Ident::new("__attempts", Span::mixed_site());
+
+        // Options are evaluated once, `when` after the first attempt to know the type of the value
+        let max_ident = Ident::new("__max", Span::mixed_site());
+        let backoff_ident = Ident::new("__backoff", Span::mixed_site());
+        let when_ident = Ident::new("__when", Span::mixed_site());
+        let max_let = max
+            .as_ref()
+            .map(|max| quote! { let #max_ident: u32 = #max; });
+        let backoff_let = backoff.as_ref().map(|backoff| {
+            quote! {
+                #[allow(unused_mut)]
+                let mut #backoff_ident = #krate::__private::retry_backoff(#backoff);
+            }
+        });
+        let when_let = when.as_ref().map(|_| {
+            quote! {
+                let mut #when_ident = ::core::option::Option::None;
+            }
+        });
+
+        let retryable = match when {
+            Some(when) => quote! {
+                #when_ident.get_or_insert_with(|| #krate::__private::retry_when(&#value, #when))(&#value)
+            },
+            None => quote! { #krate::Retryable::is_retryable(&#value) },
+        };
+        let below_max = max.as_ref().map(|_| quote! { && #attempts < #max_ident });
+        let backoff_call = backoff
+            .as_ref()
+            .map(|_| quote! { #backoff_ident(#attempts); });
+        // Attempts are only counted for `max` and `backoff`, and can't overflow without `max`
+        let (attempts_let, count) = match max.is_some() || backoff.is_some() {
+            true => (
+                quote! { let mut #attempts: u32 = 0; },
+                quote! { #attempts = #attempts.saturating_add(1); },
+            ),
+            false => (quote! {}, quote! {}),
+        };
+
+        tokens.extend(quote! {{
+            #max_let
+            #backoff_let
+            #when_let
+            #attempts_let
+            let #value = loop {
+                let #value = #expr;
+                #count
+                if #retryable #below_max {
+                    #backoff_call
+                    continue;
+                }
+                break #value;
+            };
+            #propagation
+        }});
+    }
+}
@@ -1,3 +1,8 @@
+use crate::{Bad, ExactlyTwoDistinctVariants, FfiResult, Good, TwoStates};
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
 pub trait __GetIndex {
     fn get_index(&self) -> usize;
 }
@@ -16,6 +21,276 @@ pub trait __BadIndex: __GetIndex {
     }
 }
 
+/// Variants marked `#[neutral]` or `#[propagate(skip)]`
+pub trait __NeutralIndex: __GetIndex {
+    fn neutral_indexes(&self) -> &'static [u8];
+    fn is_neutral(&self) -> bool {
+        get_bit_at(self.neutral_indexes(), self.get_index())
+    }
+}
+
+#[cold]
+#[track_caller]
+pub fn expect_failed(message: &str, value: &dyn Debug) -> ! {
+    panic!("{message}: {value:?}")
+}
+
+#[cold]
+#[track_caller]
+pub fn unreachable_value(value: &dyn Debug) -> ! {
+    panic!("internal error: entered unreachable code: {value:?}")
+}
+
+#[cold]
+#[track_caller]
+pub fn todo_value(value: &dyn Debug) -> ! {
+    panic!("not yet implemented: {value:?}")
+}
+
+/// The type of a value, for autoref dispatch. Impls on `&KindOf<T>` and `&&KindOf<T>` never
+/// overlap, even when the value is itself a reference.
+pub struct KindOf<T>(PhantomData<T>);
+
+impl<T> KindOf<T> {
+    #[inline]
+    pub fn of(_: &T) -> Self {
+        KindOf(PhantomData)
+    }
+}
+
+/// `good!(x; expect "...")` reports the bad value of a two-state enum, and the whole enum
+/// otherwise. `(&KindOf::of(&value)).__dump_kind()` picks `TwoStatesDump` for two-state
+/// enums, and autorefs to `WholeDump` otherwise.
+pub struct TwoStatesDump;
+pub struct WholeDump;
+
+pub trait DumpTwoStates: Sized {
+    #[inline]
+    fn __dump_kind(self) -> TwoStatesDump {
+        TwoStatesDump
+    }
+}
+impl<T: ExactlyTwoDistinctVariants> DumpTwoStates for &KindOf<T> {}
+
+pub trait DumpWhole: Sized {
+    #[inline]
+    fn __dump_kind(self) -> WholeDump {
+        WholeDump
+    }
+}
+impl<T> DumpWhole for &&KindOf<T> {}
+
+impl TwoStatesDump {
+    #[inline]
+    pub fn bad_value<B, T: Bad<B>>(self, value: T) -> B {
+        match value.bad() {
+            Err(bad) => bad,
+            Ok(_) => unreachable!(),
+        }
+    }
+
+    #[inline]
+    pub fn good_value<G, T: Good<G>>(self, value: T) -> G {
+        match value.good() {
+            Ok(good) => good,
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+impl WholeDump {
+    #[inline]
+    pub fn bad_value<T>(self, value: T) -> T {
+        value
+    }
+
+    #[inline]
+    pub fn good_value<T>(self, value: T) -> T {
+        value
+    }
+}
+
+/// `=> inspect` borrows the enum behind the scrutinee, which may itself be a reference.
+/// `(&KindOf::of(&value)).__borrow_kind()` picks `ReferenceBorrow` for `&E` and `&mut E`, and
+/// autorefs to `OwnedBorrow` otherwise, so both end up with a `&E`.
+pub struct ReferenceBorrow;
+pub struct OwnedBorrow;
+
+pub trait BorrowReference: Sized {
+    #[inline]
+    fn __borrow_kind(self) -> ReferenceBorrow {
+        ReferenceBorrow
+    }
+}
+impl<T: ?Sized> BorrowReference for &KindOf<&T> {}
+impl<T: ?Sized> BorrowReference for &KindOf<&mut T> {}
+
+pub trait BorrowOwned: Sized {
+    #[inline]
+    fn __borrow_kind(self) -> OwnedBorrow {
+        OwnedBorrow
+    }
+}
+impl<T> BorrowOwned for &&KindOf<T> {}
+
+impl ReferenceBorrow {
+    #[inline]
+    pub fn borrow<T: ?Sized, R: Deref<Target = T>>(self, value: &R) -> &T {
+        value
+    }
+}
+
+impl OwnedBorrow {
+    #[inline]
+    pub fn borrow<T>(self, value: &T) -> &T {
+        value
+    }
+}
+
+/// `good!(x => inspect ...)` keeps the two-state enum to return it as is, and passes the
+/// closure the bad value of a reference to it, like `&E` for a `Result<T, E>`
+#[inline]
+pub fn inspect_bad<'a, G, B, T, R>(value: &'a T) -> R
+where
+    T: TwoStates<G, B>,
+    &'a T: Bad<R>,
+{
+    match value.bad() {
+        Err(bad) => bad,
+        Ok(_) => unreachable!(),
+    }
+}
+
+/// `bad!(x => inspect ...)` passes the good value of a reference to the enum
+#[inline]
+pub fn inspect_good<'a, G, B, T, R>(value: &'a T) -> R
+where
+    T: TwoStates<G, B>,
+    &'a T: Good<R>,
+{
+    match value.good() {
+        Ok(good) => good,
+        Err(_) => unreachable!(),
+    }
+}
+
+/// Gives the `when` closure of `retry!` the type of the retried value
+#[inline]
+pub fn retry_when<T, F: FnMut(&T) -> bool>(_value: &T, when: F) -> F {
+    when
+}
+
+/// Gives the `backoff` closure of `retry!` the type of the attempt count
+#[inline]
+pub fn retry_backoff<F: FnMut(u32)>(backoff: F) -> F {
+    backoff
+}
+
+/// A `rest` arm of `match_good!` after both a `good` and a `bad` arm can't be reached on
+/// two-state enums. `(&KindOf::of(&value)).__rest_kind()` picks `TwoStatesRest` for them, and
+/// autorefs to `OtherRest` otherwise, and only `OtherRest` implements `RestArmIsReachable`.
+pub struct TwoStatesRest;
+pub struct OtherRest;
+
+pub trait RestKindTwoStates: Sized {
+    #[inline]
+    fn __rest_kind(self) -> TwoStatesRest {
+        TwoStatesRest
+    }
+}
+impl<T: ExactlyTwoDistinctVariants> RestKindTwoStates for &KindOf<T> {}
+
+pub trait RestKindOther: Sized {
+    #[inline]
+    fn __rest_kind(self) -> OtherRest {
+        OtherRest
+    }
+}
+impl<T> RestKindOther for &&KindOf<T> {}
+
+#[diagnostic::on_unimplemented(
+    message = "the `rest(..)` arm is unreachable, the `good(..)` and `bad(..)` arms already match every value of a two-state enum",
+    label = "unreachable for a `TwoStates` enum",
+    note = "remove the `rest(..)` arm"
+)]
+pub trait RestArmIsReachable {}
+impl RestArmIsReachable for OtherRest {}
+
+#[inline]
+pub fn rest_arm_is_reachable<K: RestArmIsReachable>(_: K) {}
+
+/// `good!(x => boxed)` boxes an `Error` payload as is, and wraps any other `Display` payload in
+/// a `BadError`. The payload type is known at the call site, so `(&payload).__box_kind()` picks
+/// `BoxError` when it applies, and autorefs to `BoxDisplay` otherwise.
+#[cfg(feature = "alloc")]
+mod boxed {
+    use crate::BadError;
+    use alloc::boxed::Box;
+    use core::error::Error;
+    use core::fmt::{Debug, Display};
+
+    pub type BoxedError = Box<dyn Error + Send + Sync>;
+
+    pub struct ErrorTag;
+    pub struct DisplayTag;
+
+    pub trait BoxError: Sized {
+        #[inline]
+        fn __box_kind(self) -> ErrorTag {
+            ErrorTag
+        }
+    }
+    impl<E: Error + Send + Sync + 'static> BoxError for &E {}
+
+    pub trait BoxDisplay: Sized {
+        #[inline]
+        fn __box_kind(self) -> DisplayTag {
+            DisplayTag
+        }
+    }
+    impl<D: Debug + Display + Send + Sync + 'static> BoxDisplay for &&D {}
+
+    impl ErrorTag {
+        #[inline]
+        pub fn boxed<E: Error + Send + Sync + 'static>(self, error: E) -> BoxedError {
+            Box::new(error)
+        }
+    }
+
+    impl DisplayTag {
+        #[inline]
+        pub fn boxed<D: Debug + Display + Send + Sync + 'static>(self, bad: D) -> BoxedError {
+            Box::new(BadError::new(bad))
+        }
+    }
+}
+
+/// Without `alloc`, `good!(x => boxed)` reports the name of this trait
+#[cfg(not(feature = "alloc"))]
+mod boxed {
+    pub trait BoxedErrorsRequireTheAllocFeature {}
+
+    pub struct NoAlloc;
+
+    pub trait BoxError: Sized {
+        #[inline]
+        fn __box_kind(self) -> NoAlloc {
+            NoAlloc
+        }
+    }
+    impl<T> BoxError for T {}
+
+    pub trait BoxDisplay {}
+
+    impl NoAlloc {
+        pub fn boxed<T: BoxedErrorsRequireTheAllocFeature>(self, _: T) -> ! {
+            unreachable!()
+        }
+    }
+}
+
+pub use boxed::*;
+
 fn get_bit_at(bytes: &[u8], index: usize) -> bool {
     // Div-mod by 8
     let byte_index = index >> 3;
@@ -26,10 +301,11 @@ fn get_bit_at(bytes: &[u8], index: usize) -> bool {
 
 const GOOD_INDEXES: &[u8] = &[0b01];
 const BAD_INDEXES: &[u8] = &[0b10];
+const NEUTRAL_INDEXES: &[u8] = &[0b00];
 
 macro_rules! impl_index {
     ($ty:ident[$($generics:tt)*],
-    $good_variant:ident, $bad_variant:ident,
+    $good_variant:path, $bad_variant:path,
     $good_method:ident, $bad_method:ident) => {
         impl <$($generics)*> __GetIndex for $ty <$($generics)*> {
             fn get_index(&self) -> usize {
@@ -47,6 +323,10 @@ macro_rules! impl_index {
             fn bad_indexes(&self) -> &'static [u8] { BAD_INDEXES }
             fn is_bad(&self) -> bool { self.$bad_method() }
         }
+        impl <$($generics)*> __NeutralIndex for $ty <$($generics)*> {
+            fn neutral_indexes(&self) -> &'static [u8] { NEUTRAL_INDEXES }
+            fn is_neutral(&self) -> bool { false }
+        }
     };
 }
 
@@ -55,3 +335,4 @@ use core::ops::ControlFlow::{self, Break, Continue};
 impl_index!(Result[T, E], Ok, Err, is_ok, is_err);
 impl_index!(Option[T], Some, None, is_some, is_none);
 impl_index!(ControlFlow[B, C], Continue, Break, is_continue, is_break);
+impl_index!(FfiResult[T, E], FfiResult::Ok, FfiResult::Err, is_ok, is_err);
@@ -0,0 +1,79 @@
+use core::error::Error;
+use core::fmt::{self, Debug, Display};
+
+/// Adapts the bad payload of a [`TwoStates`] value into a [`core::error::Error`]
+///
+/// `Display` and `Debug` are the ones of the payload. Built with [`BadError::from_error`],
+/// `source()` delegates to the payload, which is itself an `Error`. With the `alloc` feature,
+/// `good!(x => boxed)` returns the bad payload as a `Box<dyn Error + Send + Sync>`.
+///
+/// [`TwoStates`]: crate::TwoStates
+///
+/// ```
+/// use propagate::{good, BadError, Propagate};
+///
+/// #[derive(Propagate)]
+/// enum Lookup {
+///     #[good]
+///     Found(u32),
+///     #[bad]
+///     Missing(&'static str),
+/// }
+///
+/// fn find(lookup: Lookup) -> Result<u32, BadError<&'static str>> {
+///     Ok(good!(lookup => |key| Err(BadError::new(key))))
+/// }
+/// assert_eq!(find(Lookup::Missing("port")).unwrap_err().to_string(), "port");
+/// ```
+pub struct BadError<B> {
+    bad: B,
+    source: fn(&B) -> Option<&(dyn Error + 'static)>,
+}
+
+impl<B: Debug + Display> BadError<B> {
+    /// Wraps a payload without a source
+    pub fn new(bad: B) -> Self {
+        BadError {
+            bad,
+            source: |_| None,
+        }
+    }
+}
+
+impl<B: Error + 'static> BadError<B> {
+    /// Wraps an error payload, `source()` delegates to it
+    pub fn from_error(bad: B) -> Self {
+        BadError {
+            bad,
+            source: |bad| bad.source(),
+        }
+    }
+}
+
+impl<B> BadError<B> {
+    pub fn get(&self) -> &B {
+        &self.bad
+    }
+
+    pub fn into_inner(self) -> B {
+        self.bad
+    }
+}
+
+impl<B: Debug> Debug for BadError<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.bad, f)
+    }
+}
+
+impl<B: Display> Display for BadError<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.bad, f)
+    }
+}
+
+impl<B: Debug + Display> Error for BadError<B> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        (self.source)(&self.bad)
+    }
+}
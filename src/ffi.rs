@@ -0,0 +1,64 @@
+/// A `Result` with a stable C layout, to return from `extern "C"` functions
+///
+/// `#[repr(C, u8)]` lays it out as a C struct of a `uint8_t` tag followed by a union of the
+/// payloads. The tag is `0` for `Ok` and `1` for `Err`, so C sees:
+///
+/// ```c
+/// struct FfiResult_T_E {
+///     uint8_t tag;
+///     union { T ok; E err; } value;
+/// };
+/// ```
+///
+/// It is a [`TwoStates`] enum like `Result`, and converts losslessly to and from `Result`.
+///
+/// [`TwoStates`]: crate::TwoStates
+/// ```
+/// use propagate::{good, Bad, FfiResult};
+///
+/// #[no_mangle]
+/// pub extern "C" fn parse_port(byte: u8) -> FfiResult<u16, i32> {
+///     let digit = good!((byte as char).to_digit(10) => |_| Bad(-1));
+///     FfiResult::Ok(8000 + digit as u16)
+/// }
+/// assert_eq!(Result::from(parse_port(b'1')), Ok(8001));
+/// assert_eq!(Result::from(parse_port(b'x')), Err(-1));
+/// ```
+#[repr(C, u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FfiResult<T, E> {
+    Ok(T) = 0,
+    Err(E) = 1,
+}
+
+impl<T, E> FfiResult<T, E> {
+    #[inline]
+    pub const fn is_ok(&self) -> bool {
+        matches!(self, FfiResult::Ok(_))
+    }
+
+    #[inline]
+    pub const fn is_err(&self) -> bool {
+        matches!(self, FfiResult::Err(_))
+    }
+}
+
+impl<T, E> From<Result<T, E>> for FfiResult<T, E> {
+    #[inline]
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(ok) => FfiResult::Ok(ok),
+            Err(err) => FfiResult::Err(err),
+        }
+    }
+}
+
+impl<T, E> From<FfiResult<T, E>> for Result<T, E> {
+    #[inline]
+    fn from(result: FfiResult<T, E>) -> Self {
+        match result {
+            FfiResult::Ok(ok) => Ok(ok),
+            FfiResult::Err(err) => Err(err),
+        }
+    }
+}
@@ -1,11 +1,29 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod bad_error;
+mod ffi;
 mod traits;
 #[macro_use]
 mod macros;
 #[doc(hidden)]
 pub mod __private;
+#[cfg(feature = "std")]
+mod process;
+mod ptr;
+#[cfg(feature = "std")]
+mod sync;
+#[cfg(feature = "testing")]
+pub mod testing;
 
+pub use bad_error::BadError;
+pub use ffi::FfiResult;
+#[cfg(feature = "std")]
+pub use process::ExitFailure;
 pub use propagate_derive::*;
 pub use traits::*;
 
@@ -26,4 +44,7 @@ where
 }
 
 #[cfg(feature = "enum_index")]
-pub use __private::{__BadIndex as BadIndex, __GetIndex as GetIndex, __GoodIndex as GoodIndex};
+pub use __private::{
+    __BadIndex as BadIndex, __GetIndex as GetIndex, __GoodIndex as GoodIndex,
+    __NeutralIndex as NeutralIndex,
+};
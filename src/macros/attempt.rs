@@ -0,0 +1,60 @@
+/// Stable emulation of a `try` block
+///
+/// Inside the block, a bare `good!(x)` or `bad!(x)` breaks out of the block instead of returning
+/// from the function, with the propagated value converted through [`FromBad`] (or [`FromGood`]
+/// for `bad!`). The tail of the block is wrapped through [`FromGood`], use `()` if the block has
+/// no tail. Like `try` blocks, the type of the block usually needs an annotation.
+///
+/// Only the bare forms are rewritten, `good!(x; ...)` and `good!(x => ...)` keep their usual
+/// meaning. `good!` and `bad!` inside closures, async blocks, nested items and nested `attempt!`
+/// blocks are left alone. The value must be a [`TwoStates`] enum.
+///
+/// [`FromGood`]: crate::FromGood
+/// [`FromBad`]: crate::FromBad
+/// [`TwoStates`]: crate::TwoStates
+///
+/// # Examples
+/// ```
+/// use propagate::{attempt, good};
+/// use std::num::ParseIntError;
+///
+/// fn add(str1: &str, str2: &str) -> i8 {
+///     let sum: Result<i8, ParseIntError> = attempt! {
+///         let int1 = good!(str1.parse::<i8>());
+///         let int2 = good!(str2.parse::<i8>());
+///         int1 + int2
+///     };
+///     sum.unwrap_or(-1)
+/// }
+/// assert_eq!(add("12", "21"), 33);
+/// assert_eq!(add("12", "abc"), -1);
+/// ```
+///
+/// `return`, `continue` and `break` still leave the enclosing function or loop. As with any
+/// labelled block, a `continue` or `break` targeting a loop outside the block needs a label.
+/// ```
+/// # use propagate::{attempt, good};
+/// fn first_even_sum(pairs: &[(&str, &str)]) -> Option<i32> {
+///     'pairs: for (a, b) in pairs {
+///         let sum: Option<i32> = attempt! {
+///             let a = good!(a.parse::<i32>().ok());
+///             let b = good!(b.parse::<i32>().ok());
+///             if (a + b) % 2 != 0 {
+///                 continue 'pairs;
+///             }
+///             a + b
+///         };
+///         if sum.is_some() {
+///             return sum;
+///         }
+///     }
+///     None
+/// }
+/// assert_eq!(first_even_sum(&[("1", "x"), ("1", "2"), ("1", "3")]), Some(4));
+/// ```
+#[macro_export]
+macro_rules! attempt {
+    ($($tokens:tt)*) => {
+        $crate::__attempt!($crate $($tokens)*)
+    };
+}
@@ -0,0 +1,46 @@
+/// Tries alternatives in order, and gives the first good value
+///
+/// `first_good!(<expr>, <expr>, ...; <clause>)` evaluates the alternatives lazily, from left to
+/// right, until one of them is good. The alternatives may be of different types implementing
+/// [`Good`] with the same good value, like `Option<T>`, `Result<T, E>` and derived enums.
+///
+/// If all of them are bad, their bad values are collected into a tuple, in order, and propagated
+/// with the clause, which is any clause of [`take!`]. The `=>` closures get the tuple. The bad
+/// value of a [`TwoStates`] alternative is its bad payload, like the error of a `Result` or `()`
+/// for `None`, and the bad value of any other enum is the whole enum.
+///
+/// [`TwoStates`]: crate::TwoStates
+///
+/// [`Good`]: crate::Good
+/// [`take!`]: crate::take
+///
+/// # Examples
+/// ```
+/// use propagate::first_good;
+/// use std::num::ParseIntError;
+///
+/// fn port(env: Option<&str>, file: Result<u16, &str>) -> u16 {
+///     let parse_env = || env.map(str::parse::<u16>);
+///     first_good!(parse_env().and_then(Result::ok), file; else 8080)
+/// }
+/// assert_eq!(port(Some("80"), Ok(443)), 80);
+/// assert_eq!(port(None, Ok(443)), 443);
+/// assert_eq!(port(None, Err("no config file")), 8080);
+/// ```
+///
+/// ```
+/// # use propagate::first_good;
+/// fn port(env: Option<u16>, file: Result<u16, String>) -> Result<u16, String> {
+///     let port = first_good!(env, file => |((), file_error): ((), String)| {
+///         Err(format!("no port in the environment or file: {file_error}"))
+///     });
+///     Ok(port)
+/// }
+/// assert_eq!(port(None, Err("empty".to_owned())).unwrap_err(), "no port in the environment or file: empty");
+/// ```
+#[macro_export]
+macro_rules! first_good {
+    ($($tokens:tt)*) => {
+        $crate::__first_good!($crate $($tokens)*)
+    };
+}
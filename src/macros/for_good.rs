@@ -0,0 +1,40 @@
+/// Loops over the good values of an iterator
+///
+/// `for_good!(<pat> in <iter> <clause> { <body> })` runs the body for each item that is good,
+/// with its good value bound to the pattern. Bad items are propagated with the same clauses as
+/// [`good!`], after the iterator expression. Without a clause, or with a clause that doesn't
+/// leave the loop (like `=> do |e| ...;`), bad items are skipped with `continue`.
+/// The loop can be labelled with `for_good!('label: <pat> in ...)`.
+///
+/// Like a `for` loop, `for_good!` evaluates to `()`. If the clause breaks the loop with a value,
+/// it evaluates to `Option` of the value instead, which is `None` once the iterator is exhausted.
+/// A `break` in the body then has to carry an `Option` too.
+///
+/// [`good!`]: crate::good
+///
+/// # Examples
+/// ```
+/// use propagate::for_good;
+///
+/// let mut errors = Vec::new();
+/// let mut sum = 0;
+/// for_good!(num in ["1", "x", "2"].map(str::parse::<i32>) => do |e| errors.push(e); {
+///     sum += num;
+/// });
+/// assert_eq!(sum, 3);
+/// assert_eq!(errors.len(), 1);
+/// ```
+///
+/// ```
+/// # use propagate::for_good;
+/// let first_error = for_good!(num in ["1", "x", "2"].map(str::parse::<i32>) => break |e| e {
+///     println!("{num}");
+/// });
+/// assert!(first_error.is_some());
+/// ```
+#[macro_export]
+macro_rules! for_good {
+    ($($tokens:tt)*) => {
+        $crate::__for_good!($crate $($tokens)*)
+    };
+}
@@ -29,6 +29,28 @@
 /// }
 /// ```
 ///
+/// ### Pick the payload type
+/// With overloaded `#[good]` variants, `as` after the enum picks the `Good<T>` impl, so the call
+/// doesn't need a type annotation. It works with every clause below.
+/// Wrap the expression in parentheses to propagate the result of a cast instead.
+/// A primitive payload type reads like a cast, so it is parenthesized too, `good!(x as (u32))`.
+/// ```
+/// use propagate::{good, Propagate};
+/// #[derive(Propagate)]
+/// enum Reply {
+///     #[good]
+///     Text(String),
+///     #[good]
+///     Code(u32),
+///     #[bad]
+///     Error(String),
+/// }
+///
+/// fn print_code(reply: &Reply) {
+///     println!("{}", good!(reply as &u32;));
+/// }
+/// ```
+///
 /// ### Propagate an expression
 /// Tries to get the inner value or return a default value.
 ///
@@ -156,7 +178,7 @@
 ///
 /// fn main() {
 ///     let err_code: u32 = loop {
-///         let msg: String = good!(get_message() => full break |resp| resp.status_code());
+///         let msg: String = good!(get_message() => full break |resp: HttpResponse| resp.status_code());
 ///         // Do something with message
 ///     };
 ///     // Handle error code
@@ -182,18 +204,108 @@
 ///     (sum, errors)
 /// }
 /// ```
+///
+/// ### Inspect the propagated value
+/// Tries to get the inner value, or pass the bad value to a closure, then return the original
+/// enum as is, like a plain `good!(your_enum)` does. The closure borrows the bad value, like
+/// `&E` for a `Result<T, E>`, or with `=> full inspect` it gets a reference to the whole enum.
+///
+/// Add a semicolon after the closure to continue, break or return something else instead.
+///
+/// ```
+/// # use propagate::good;
+/// use std::num::ParseIntError;
+///
+/// fn parse_all(strings: &[&str]) -> Vec<i32> {
+///     let mut nums = Vec::new();
+///     for s in strings {
+///         let num = good!(s.parse::<i32>() => inspect |e| eprintln!("{s}: {e}"); continue);
+///         nums.push(num);
+///     }
+///     nums
+/// }
+///
+/// fn parse_first(strings: &[&str]) -> Result<i32, ParseIntError> {
+///     let first = good!(strings[0].parse::<i32>() => inspect |e| eprintln!("first: {e}"));
+///     Ok(first * 2)
+/// }
+/// assert_eq!(parse_all(&["1", "x", "2"]), [1, 2]);
+/// assert!(parse_first(&["x"]).is_err());
+/// ```
+///
+/// ### Panic with the propagated value
+/// Tries to get the inner value, or panic at the macro call site.
+///
+/// `; expect "message"`, `; unreachable` and `; todo` print the bad value of a [`TwoStates`]
+/// enum with `Debug`, and the whole enum otherwise.
+/// After a fat arrow (`=>`), the enum must be [`TwoStates`], and `=> full` prints the whole enum.
+/// `debug_expect "message"; <clause>` only panics in debug builds, and propagates with the
+/// clause after the semicolon in release builds.
+///
+/// `unreachable` and `todo` are keywords even when a local of that name is in scope, write
+/// `; (todo)` or `=> (todo)` to return the local or apply it as a closure instead.
+///
+/// ```should_panic
+/// # use propagate::good;
+/// fn load_config(path: &str) -> String {
+///     let res: Result<String, &str> = Err("no such file");
+///     // Panics with `loading config: "no such file"`
+///     good!(res => expect "loading config")
+/// }
+/// # load_config("config.toml");
+/// ```
+///
+/// ```
+/// # use propagate::good;
+/// fn sum_of_strings(strings: &[&str]) -> i32 {
+///     let mut sum = 0;
+///     for s in strings {
+///         sum += good!(s.parse::<i32>() => debug_expect "parsing a number"; continue);
+///     }
+///     sum
+/// }
+/// ```
+///
+/// ### Box the bad value as an error
+/// Tries to get the inner value, or return the bad value of a [`TwoStates`] enum as a
+/// `Box<dyn Error + Send + Sync>`, converted to the return type through `FromBad`.
+/// Requires the `alloc` feature.
+///
+/// An `Error` is boxed as is, any other `Display` value is wrapped in a [`BadError`].
+/// `boxed` is a keyword even when a local of that name is in scope, write `=> (boxed)` to apply
+/// the local as a closure instead.
+///
+/// [`BadError`]: crate::BadError
+#[cfg_attr(feature = "alloc", doc = "```")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// # use propagate::good;
+/// use std::error::Error;
+/// fn parse_port(s: &str) -> Result<u16, Box<dyn Error + Send + Sync>> {
+///     Ok(good!(s.parse::<u16>() => boxed))
+/// }
+/// ```
+///
+/// ### Recover the good value
+/// Tries to get the inner value, or take it back out of the error through [`Recover`].
+///
+/// With the `std` feature, `; recover` gets the guard out of a poisoned lock.
+///
+/// `recover` is a keyword even when a local of that name is in scope, write `; (recover)` to
+/// return the local instead.
+///
+/// [`Recover`]: crate::Recover
+#[cfg_attr(feature = "std", doc = "```")]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+/// # use propagate::good;
+/// use std::sync::Mutex;
+/// fn increment(counter: &Mutex<u32>) {
+///     let mut count = good!(counter.lock(); recover);
+///     *count += 1;
+/// }
+/// ```
 #[macro_export]
 macro_rules! good {
-    ($enum_:expr) => {
-        $crate::__take!(Ok, Err, $crate::Good::good($enum_))
-    };
-    ($enum_:expr; $($propagate:tt)*) => {
-        $crate::__take!(Ok, Err, $crate::Good::good($enum_); $($propagate)*)
-    };
-    ($enum_:expr => full $($propagate_closure:tt)*) => {
-        $crate::__take!(Ok, Err, $crate::Good::good($enum_) => $($propagate_closure)*)
-    };
-    ($enum_:expr => $($propagate_closure:tt)*) => {
-        $crate::__take!(Ok, Err, $crate::TwoStates::two_states($enum_) => $($propagate_closure)*)
+    ($($tokens:tt)*) => {
+        $crate::__propagation!($crate good $($tokens)*)
     };
 }
@@ -0,0 +1,7 @@
+#[macro_export]
+macro_rules! is_neutral {
+    ($enum_:expr) => {{
+        use $crate::__private::__NeutralIndex;
+        $enum_.is_neutral()
+    }};
+}
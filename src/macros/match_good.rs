@@ -0,0 +1,72 @@
+/// Matches a value by its classification instead of its variants
+///
+/// `match_good!(<expr> { good(<pat>) => ..., bad(<pat>) => ..., rest(<pat>) => ... })` tries the
+/// arms in order: a `good` arm takes the good value through [`Good`], a `bad` arm the bad value
+/// through [`Bad`], and the value falls through to the next arm otherwise. The last arm must be
+/// `rest`, which takes whatever is left of the value.
+///
+/// Annotate the pattern, like `good(s: &str)`, to pick one of several good or bad payload types.
+///
+/// A [`TwoStates`] enum is matched exhaustively by one `good` and one `bad` arm, without `rest`.
+/// A `rest` arm after both of them would be unreachable, so it fails to compile. After only a
+/// `good` or only a `bad` arm, `rest` takes the other variant.
+///
+/// [`Good`]: crate::Good
+/// [`Bad`]: crate::Bad
+/// [`TwoStates`]: crate::TwoStates
+///
+/// # Examples
+/// ```
+/// use propagate::{match_good, Propagate};
+///
+/// #[derive(Propagate)]
+/// enum Message {
+///     #[good]
+///     Text(String),
+///     #[good]
+///     Code(u32),
+///     #[bad]
+///     Error(String),
+///     Ping,
+/// }
+///
+/// fn describe(msg: Message) -> String {
+///     match_good!(msg {
+///         good(text: String) => text,
+///         good(code: u32) => format!("code {code}"),
+///         bad(error) => format!("error: {error}"),
+///         rest(_) => "ping".to_owned(),
+///     })
+/// }
+/// assert_eq!(describe(Message::Code(7)), "code 7");
+/// assert_eq!(describe(Message::Ping), "ping");
+/// ```
+///
+/// ```
+/// # use propagate::match_good;
+/// fn parse(s: &str) -> i32 {
+///     match_good!(s.parse::<i32>() {
+///         good(n) => n,
+///         bad(_) => -1,
+///     })
+/// }
+/// assert_eq!(parse("x"), -1);
+/// ```
+///
+/// ```
+/// # use propagate::match_good;
+/// fn parse(s: &str) -> i32 {
+///     match_good!(s.parse::<i32>() {
+///         good(n) => n,
+///         rest(_) => -1,
+///     })
+/// }
+/// assert_eq!(parse("2"), 2);
+/// assert_eq!(parse("x"), -1);
+/// ```
+#[macro_export]
+macro_rules! match_good {
+    ($($tokens:tt)*) => {
+        $crate::__match_good!($crate $($tokens)*)
+    };
+}
@@ -1,9 +1,16 @@
 mod __helper_macros;
+mod attempt;
 mod bad;
+mod first_good;
+mod for_good;
 mod good;
 mod is_bad;
 mod is_good;
+mod is_neutral;
+mod match_good;
 mod reject;
 mod reject_bad;
 mod reject_good;
+mod retry;
 mod take;
+mod while_good;
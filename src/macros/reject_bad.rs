@@ -1,25 +1,18 @@
 #[macro_export]
 macro_rules! reject_bad {
-    ($enum_:expr) => {
-        match $crate::Bad::bad($enum_) {
-            Ok(v) => return v,
-            Err(enum_) => enum_,
-        }
-    };
-    ($enum_:expr; $($propagate:tt)*) => {
-        match $crate::is_bad!($enum_) {
-            #[allow(unreachable_code)]
-            #[allow(clippy::diverging_sub_expression)]
-            true => $crate::__propagate!($($propagate)*),
-            false => $enum_,
-        }
-    };
-    ($enum_:expr => $($propagate_closure:tt)*) => {
-        match $crate::Bad::bad($enum_) {
-            #[allow(unreachable_code)]
-            #[allow(clippy::diverging_sub_expression)]
-            Err(v) => $crate::__propagate_closure!(v => $($propagate_closure)*),
-            Ok(enum_) => enum_,
-        }
-    };
+    ($enum_:expr; $($propagate:tt)*) => {{
+        // Bind once, the expression may have side effects or move its value
+        let __res = match $enum_ {
+            __enum => match $crate::is_bad!(__enum) {
+                true => ::core::result::Result::Err(__enum),
+                false => ::core::result::Result::Ok(__enum),
+            },
+        };
+        $crate::__propagation!($crate reject __res; $($propagate)*)
+    }};
+    ($enum_:expr $(=> $($propagate_closure:tt)*)?) => {{
+        // `Bad::bad` already keeps the enum in `Ok` and rejects the bad value in `Err`
+        let __res = $crate::Bad::bad($enum_);
+        $crate::__propagation!($crate reject __res $(=> $($propagate_closure)*)?)
+    }};
 }
@@ -1,25 +1,20 @@
 #[macro_export]
 macro_rules! reject_good {
-    ($enum_:expr) => {
-        match $crate::Good::good($enum_) {
-            Ok(v) => return v,
-            Err(enum_) => enum_,
-        }
-    };
-    ($enum_:expr; $($propagate:tt)*) => {
-        match $crate::is_good!($enum_) {
-            #[allow(unreachable_code)]
-            #[allow(clippy::diverging_sub_expression)]
-            true => $crate::__propagate!($($propagate)*),
-            false => $enum_,
-        }
-    };
-    ($enum_:expr => $($propagate_closure:tt)*) => {
-        match $crate::Good::good($enum_) {
-            #[allow(unreachable_code)]
-            #[allow(clippy::diverging_sub_expression)]
-            Ok(v) => $crate::__propagate_closure!(v => $($propagate_closure)*),
-            Err(enum_) => enum_,
-        }
-    };
+    ($enum_:expr; $($propagate:tt)*) => {{
+        // Bind once, the expression may have side effects or move its value
+        let __res = match $enum_ {
+            __enum => match $crate::is_good!(__enum) {
+                true => ::core::result::Result::Err(__enum),
+                false => ::core::result::Result::Ok(__enum),
+            },
+        };
+        $crate::__propagation!($crate reject __res; $($propagate)*)
+    }};
+    ($enum_:expr $(=> $($propagate_closure:tt)*)?) => {{
+        let __res = match $crate::Good::good($enum_) {
+            ::core::result::Result::Ok(v) => ::core::result::Result::Err(v),
+            ::core::result::Result::Err(__enum) => ::core::result::Result::Ok(__enum),
+        };
+        $crate::__propagation!($crate reject __res $(=> $($propagate_closure)*)?)
+    }};
 }
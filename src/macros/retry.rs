@@ -0,0 +1,59 @@
+/// Evaluates an expression again while it is retryable, then propagates like [`good!`]
+///
+/// `retry!(<expr>; <options>; <clause>)` evaluates the expression until its value isn't
+/// retryable, then tries to get its good value with the clause, which is any clause of
+/// [`good!`]. Without options, the value is classified by the [`Retryable`] trait.
+///
+/// Options are comma separated, and each is evaluated once:
+/// - `max = <u32>`: evaluate the expression at most this many times, the last value is
+///   propagated even if it is still retryable. The expression is always evaluated once, so a
+///   literal `max = 0` is rejected, and a `max` computed at run time as 0 acts like 1
+/// - `backoff = |attempt: u32| ...`: called before each new attempt, with the number of attempts
+///   so far, saturating at `u32::MAX`. Nothing sleeps by itself, which keeps `retry!` usable in
+///   `no_std`
+/// - `when = |value: &T| ...`: retry while the predicate holds for the whole value, instead of
+///   [`Retryable`]
+///
+/// [`good!`]: crate::good
+/// [`Retryable`]: crate::Retryable
+///
+/// # Examples
+/// ```
+/// use propagate::{retry, Propagate};
+///
+/// #[derive(Propagate, Debug, PartialEq)]
+/// enum Read {
+///     #[good]
+///     Byte(u8),
+///     #[bad(retryable)]
+///     WouldBlock,
+///     #[bad]
+///     Closed,
+/// }
+///
+/// fn read_byte(polls: &mut impl Iterator<Item = Read>) -> Read {
+///     let byte = retry!(polls.next().unwrap(); max = 5, backoff = |_| core::hint::spin_loop());
+///     Read::Byte(byte + 1)
+/// }
+///
+/// let mut polls = [Read::WouldBlock, Read::WouldBlock, Read::Byte(1)].into_iter();
+/// assert_eq!(read_byte(&mut polls), Read::Byte(2));
+/// let mut polls = [Read::WouldBlock, Read::Closed].into_iter();
+/// assert_eq!(read_byte(&mut polls), Read::Closed);
+/// ```
+///
+/// ```
+/// # use propagate::retry;
+/// fn parse_with_retries(inputs: &[&str]) -> i32 {
+///     let mut inputs = inputs.iter();
+///     retry!(inputs.next().map_or(Ok(-1), |s| s.parse::<i32>()); when = Result::is_err; else 0)
+/// }
+/// assert_eq!(parse_with_retries(&["x", "y", "3"]), 3);
+/// assert_eq!(parse_with_retries(&["x"]), -1);
+/// ```
+#[macro_export]
+macro_rules! retry {
+    ($($tokens:tt)*) => {
+        $crate::__retry!($crate $($tokens)*)
+    };
+}
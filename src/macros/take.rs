@@ -2,23 +2,23 @@
 macro_rules! take {
     ($enum_:expr, $variant:path[] $($propagation:tt)*) => {{
         let __res = match $enum_ {
-            $variant => Ok(()),
-            _ => Err($enum_),
+            $variant => ::core::result::Result::Ok(()),
+            __enum => ::core::result::Result::Err(__enum),
         };
-        $crate::__take!(Ok, Err, __res $($propagation)*)
+        $crate::__propagation!($crate take __res $($propagation)*)
     }};
     ($enum_:expr, $variant:path[$arg:ident] $($propagation:tt)*) => {{
         let __res = match $enum_ {
-            $variant($arg) => Ok($arg),
-            _ => Err($enum_),
+            $variant($arg) => ::core::result::Result::Ok($arg),
+            __enum => ::core::result::Result::Err(__enum),
         };
-        $crate::__take!(Ok, Err, __res $($propagation)*)
+        $crate::__propagation!($crate take __res $($propagation)*)
     }};
     ($enum_:expr, $variant:path[$arg:ident $(,$args:ident)+] $($propagation:tt)*) => {{
         let __res = match $enum_ {
-            $variant($arg, $($args, )+) => Ok(($arg, $($args, )+)),
-            _ => Err($enum_),
+            $variant($arg, $($args, )+) => ::core::result::Result::Ok(($arg, $($args, )+)),
+            __enum => ::core::result::Result::Err(__enum),
         };
-        $crate::__take!(Ok, Err, __res $($propagation)*)
+        $crate::__propagation!($crate take __res $($propagation)*)
     }};
 }
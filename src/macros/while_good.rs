@@ -0,0 +1,27 @@
+/// Loops while an expression is good
+///
+/// `while_good!(let <pat> = <expr> { <body> })` evaluates the expression, binds its good value to
+/// the pattern and runs the body, until the expression is bad. The loop then evaluates to the bad
+/// value, so the expression must be a [`TwoStates`] enum. A `break` in the body has to carry a
+/// bad value too. The loop can be labelled with `while_good!('label: let <pat> = ...)`.
+///
+/// [`TwoStates`]: crate::TwoStates
+///
+/// # Examples
+/// ```
+/// use propagate::while_good;
+///
+/// let mut queue = vec![Ok(1), Ok(2), Err("closed"), Ok(3)].into_iter();
+/// let mut received = Vec::new();
+/// let reason = while_good!(let msg = queue.next().unwrap() {
+///     received.push(msg);
+/// });
+/// assert_eq!(received, [1, 2]);
+/// assert_eq!(reason, "closed");
+/// ```
+#[macro_export]
+macro_rules! while_good {
+    ($($tokens:tt)*) => {
+        $crate::__while_good!($crate $($tokens)*)
+    };
+}
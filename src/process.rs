@@ -0,0 +1,158 @@
+use crate::{Bad, ExactlyTwoDistinctVariants, FromBad, FromGood, Good};
+use core::fmt;
+use std::process::{ExitCode, ExitStatus, Output};
+use std::vec::Vec;
+
+/// Why a process didn't exit successfully, with the `std` feature
+///
+/// An [`ExitStatus`] is good when it is successful, and bad with an [`ExitFailure`] otherwise.
+/// An [`Output`] is classified by its status, and its good value is the captured stdout.
+/// [`ExitCode`] is built from `()` as success, and from a `u8` or an [`ExitFailure`] as failure.
+///
+/// ```
+/// use propagate::{good, ExitFailure};
+/// use std::process::{Command, ExitCode};
+///
+/// fn main() -> ExitCode {
+///     let status = good!(Command::new("true").status() => |_| ExitCode::from(127));
+///     good!(status => |failure: ExitFailure| {
+///         eprintln!("failed with {failure}");
+///         ExitCode::from(failure)
+///     });
+///     ExitCode::SUCCESS
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExitFailure {
+    /// Exited with a non-zero code
+    Code(i32),
+    /// Terminated by a signal, on Unix
+    Signal(i32),
+    /// Failed without an exit code or a signal, like a stopped process on Unix, or on platforms
+    /// that report neither
+    Other,
+}
+
+impl ExitFailure {
+    fn of(status: ExitStatus) -> Self {
+        if let Some(code) = status.code() {
+            return ExitFailure::Code(code);
+        }
+        #[cfg(unix)]
+        if let Some(signal) = std::os::unix::process::ExitStatusExt::signal(&status) {
+            return ExitFailure::Signal(signal);
+        }
+        ExitFailure::Other
+    }
+}
+
+impl fmt::Display for ExitFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExitFailure::Code(code) => write!(f, "exit code {code}"),
+            ExitFailure::Signal(signal) => write!(f, "signal {signal}"),
+            ExitFailure::Other => write!(f, "unknown failure"),
+        }
+    }
+}
+
+impl std::error::Error for ExitFailure {}
+
+/// The code a shell would report: the exit code, or `128 + signal`. Other failures, and codes
+/// that don't fit in a `u8` or would read as success, become `1`.
+impl From<ExitFailure> for ExitCode {
+    fn from(failure: ExitFailure) -> Self {
+        let code = match failure {
+            ExitFailure::Code(code) => u8::try_from(code),
+            ExitFailure::Signal(signal) => u8::try_from(128 + i64::from(signal)),
+            ExitFailure::Other => return ExitCode::FAILURE,
+        };
+        ExitCode::from(code.ok().filter(|code| *code != 0).unwrap_or(1))
+    }
+}
+
+impl Good<()> for ExitStatus {
+    #[inline]
+    fn good(self) -> Result<(), Self> {
+        match self.success() {
+            true => Ok(()),
+            false => Err(self),
+        }
+    }
+}
+
+impl Bad<ExitFailure> for ExitStatus {
+    #[inline]
+    fn bad(self) -> Result<Self, ExitFailure> {
+        match self.success() {
+            true => Ok(self),
+            false => Err(ExitFailure::of(self)),
+        }
+    }
+}
+
+impl Good<Vec<u8>> for Output {
+    #[inline]
+    fn good(self) -> Result<Vec<u8>, Self> {
+        match self.status.success() {
+            true => Ok(self.stdout),
+            false => Err(self),
+        }
+    }
+}
+
+impl<'a> Good<&'a [u8]> for &'a Output {
+    #[inline]
+    fn good(self) -> Result<&'a [u8], Self> {
+        match self.status.success() {
+            true => Ok(&self.stdout),
+            false => Err(self),
+        }
+    }
+}
+
+impl Bad<ExitFailure> for Output {
+    #[inline]
+    fn bad(self) -> Result<Self, ExitFailure> {
+        match self.status.success() {
+            true => Ok(self),
+            false => Err(ExitFailure::of(self.status)),
+        }
+    }
+}
+
+impl Bad<ExitFailure> for &Output {
+    #[inline]
+    fn bad(self) -> Result<Self, ExitFailure> {
+        match self.status.success() {
+            true => Ok(self),
+            false => Err(ExitFailure::of(self.status)),
+        }
+    }
+}
+
+// SAFETY: a status is either successful or not, and `Good` and `Bad` are implemented on that
+unsafe impl ExactlyTwoDistinctVariants for ExitStatus {}
+unsafe impl ExactlyTwoDistinctVariants for Output {}
+unsafe impl ExactlyTwoDistinctVariants for &Output {}
+
+impl FromGood<()> for ExitCode {
+    #[inline]
+    fn from_good(_: ()) -> Self {
+        ExitCode::SUCCESS
+    }
+}
+
+impl FromBad<u8> for ExitCode {
+    #[inline]
+    fn from_bad(code: u8) -> Self {
+        ExitCode::from(code)
+    }
+}
+
+impl FromBad<ExitFailure> for ExitCode {
+    #[inline]
+    fn from_bad(failure: ExitFailure) -> Self {
+        ExitCode::from(failure)
+    }
+}
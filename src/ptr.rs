@@ -0,0 +1,80 @@
+//! Null-checked propagation for raw pointers
+//!
+//! A non-null `*const T` or `*mut T` is good with a [`NonNull<T>`], and a null pointer is bad
+//! with `()`. Pointers are built back from a `NonNull<T>` as good, and from `()` as null.
+//! `Option<NonNull<T>>` is classified as any `Option`.
+//!
+//! Pointers to unsized types, like `*const str` or `*mut [u8]`, are classified the same way.
+//! Only a pointer to a sized type can be built from `()`, since a null pointer to an unsized
+//! type would need its metadata, like the length of a slice.
+//!
+//! ```
+//! use core::ptr::NonNull;
+//! use propagate::good;
+//!
+//! fn first(values: *const u32) -> Result<u32, &'static str> {
+//!     let values: NonNull<u32> = good!(values; Err("null pointer"));
+//!     Ok(unsafe { *values.as_ptr() })
+//! }
+//! assert_eq!(first(&7), Ok(7));
+//! assert_eq!(first(core::ptr::null()), Err("null pointer"));
+//! ```
+
+use crate::__private::{__BadIndex, __GetIndex, __GoodIndex, __NeutralIndex};
+use crate::{Bad, ExactlyTwoDistinctVariants, FromBad, FromGood, Good};
+use core::ptr::{self, NonNull};
+
+const GOOD_INDEXES: &[u8] = &[0b01];
+const BAD_INDEXES: &[u8] = &[0b10];
+const NEUTRAL_INDEXES: &[u8] = &[0b00];
+
+macro_rules! impl_pointer {
+    ($ptr:ty, $null:path) => {
+        impl<T: ?Sized> Good<NonNull<T>> for $ptr {
+            #[inline]
+            fn good(self) -> Result<NonNull<T>, Self> {
+                NonNull::new(self as *mut T).ok_or(self)
+            }
+        }
+        impl<T: ?Sized> Bad<()> for $ptr {
+            #[inline]
+            fn bad(self) -> Result<Self, ()> {
+                if self.is_null() {
+                    Err(())
+                } else {
+                    Ok(self)
+                }
+            }
+        }
+        impl<T: ?Sized> FromGood<NonNull<T>> for $ptr {
+            #[inline]
+            fn from_good(good: NonNull<T>) -> Self {
+                good.as_ptr()
+            }
+        }
+        impl<T> FromBad<()> for $ptr {
+            #[inline]
+            fn from_bad(_: ()) -> Self {
+                $null()
+            }
+        }
+        unsafe impl<T: ?Sized> ExactlyTwoDistinctVariants for $ptr {}
+        impl<T: ?Sized> __GetIndex for $ptr {
+            fn get_index(&self) -> usize {
+                self.is_null() as usize
+            }
+        }
+        impl<T: ?Sized> __GoodIndex for $ptr {
+            fn good_indexes(&self) -> &'static [u8] { GOOD_INDEXES }
+        }
+        impl<T: ?Sized> __BadIndex for $ptr {
+            fn bad_indexes(&self) -> &'static [u8] { BAD_INDEXES }
+        }
+        impl<T: ?Sized> __NeutralIndex for $ptr {
+            fn neutral_indexes(&self) -> &'static [u8] { NEUTRAL_INDEXES }
+        }
+    };
+}
+
+impl_pointer!(*const T, ptr::null);
+impl_pointer!(*mut T, ptr::null_mut);
@@ -0,0 +1,75 @@
+//! Three-state classifications for the errors of `std::sync`, with the `std` feature
+//!
+//! The "try again" variants (`WouldBlock`, `Empty`, `Full`, `Timeout`) are neutral and
+//! [`Retryable`], the disconnected or poisoned variants are bad, and no variant is good.
+
+use crate::__private::{__BadIndex, __GetIndex, __GoodIndex, __NeutralIndex};
+use crate::{Bad, Retryable};
+use std::sync::mpsc::{RecvTimeoutError, TryRecvError, TrySendError};
+use std::sync::{PoisonError, TryLockError};
+
+const GOOD_INDEXES: &[u8] = &[0b00];
+const BAD_INDEXES: &[u8] = &[0b10];
+const NEUTRAL_INDEXES: &[u8] = &[0b01];
+
+macro_rules! impl_three_states {
+    ($ty:ident[$($generics:tt)*], $again:pat, $bad:pat => $payload:expr, $bad_ty:ty) => {
+        impl<$($generics)*> Bad<$bad_ty> for $ty<$($generics)*> {
+            #[inline]
+            fn bad(self) -> Result<Self, $bad_ty> {
+                match self {
+                    $bad => Err($payload),
+                    _ => Ok(self),
+                }
+            }
+        }
+        impl<$($generics)*> Retryable for $ty<$($generics)*> {
+            #[inline]
+            fn is_retryable(&self) -> bool {
+                matches!(self, $again)
+            }
+        }
+        impl<$($generics)*> __GetIndex for $ty<$($generics)*> {
+            fn get_index(&self) -> usize {
+                match self {
+                    $again => 0,
+                    _ => 1,
+                }
+            }
+        }
+        impl<$($generics)*> __GoodIndex for $ty<$($generics)*> {
+            fn good_indexes(&self) -> &'static [u8] { GOOD_INDEXES }
+        }
+        impl<$($generics)*> __BadIndex for $ty<$($generics)*> {
+            fn bad_indexes(&self) -> &'static [u8] { BAD_INDEXES }
+        }
+        impl<$($generics)*> __NeutralIndex for $ty<$($generics)*> {
+            fn neutral_indexes(&self) -> &'static [u8] { NEUTRAL_INDEXES }
+        }
+    };
+}
+
+impl_three_states!(
+    TryLockError[T],
+    TryLockError::WouldBlock,
+    TryLockError::Poisoned(poison) => poison,
+    PoisonError<T>
+);
+impl_three_states!(
+    TryRecvError[],
+    TryRecvError::Empty,
+    TryRecvError::Disconnected => (),
+    ()
+);
+impl_three_states!(
+    TrySendError[T],
+    TrySendError::Full(_),
+    TrySendError::Disconnected(value) => value,
+    T
+);
+impl_three_states!(
+    RecvTimeoutError[],
+    RecvTimeoutError::Timeout,
+    RecvTimeoutError::Disconnected => (),
+    ()
+);
@@ -0,0 +1,169 @@
+//! Assertions on the control flow taken by `good!`, `bad!` and friends
+//!
+//! Each assertion runs the macro call inside a loop inside a closure, and reports whether it
+//! yielded a value, continued the loop, broke out of it or returned from the closure.
+//! Requires the `testing` feature, and `panic = "unwind"`.
+//!
+//! ```
+//! use propagate::good;
+//! use propagate::testing::{assert_breaks, assert_continues, assert_returns, assert_yields};
+//!
+//! assert_yields!(good!(Some(1)), 1);
+//! assert_returns!(good!(None::<i32>), None::<i32>);
+//! assert_continues!(good!(None::<i32>; continue));
+//! assert_breaks!(good!(Err::<i32, &str>("oops") => break |e: &str| e.len()), 4);
+//! ```
+
+use core::fmt::{self, Debug, Display};
+use std::boxed::Box;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+
+#[doc(inline)]
+pub use crate::{assert_breaks, assert_continues, assert_returns, assert_yields};
+
+/// The control flow taken by a macro call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Branch<Y, B, R> {
+    /// The call gave a value
+    Yielded(Y),
+    /// The call continued the enclosing loop
+    Continued,
+    /// The call broke out of the enclosing loop, with a value
+    Broke(B),
+    /// The call returned from the enclosing function, with a value
+    Returned(R),
+}
+
+impl<Y, B, R> Display for Branch<Y, B, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Branch::Yielded(_) => "yielded a value",
+            Branch::Continued => "continued",
+            Branch::Broke(_) => "broke",
+            Branch::Returned(_) => "returned",
+        })
+    }
+}
+
+/// Unwinds out of the closure after the branch is recorded
+struct Escape;
+
+#[doc(hidden)]
+pub fn __catch<Y, B, R>(call: impl FnOnce(&mut Option<Branch<Y, B, R>>) -> R) -> Branch<Y, B, R> {
+    let mut branch = None;
+    match catch_unwind(AssertUnwindSafe(|| call(&mut branch))) {
+        Ok(returned) => Branch::Returned(returned),
+        Err(payload) if payload.is::<Escape>() => branch.expect("the branch is recorded"),
+        Err(payload) => resume_unwind(payload),
+    }
+}
+
+#[doc(hidden)]
+pub fn __escape<Y, B, R>(slot: &mut Option<Branch<Y, B, R>>, branch: Branch<Y, B, R>) -> ! {
+    *slot = Some(branch);
+    resume_unwind(Box::new(Escape))
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __wrong_branch<Y, B, R>(expected: &str, branch: Branch<Y, B, R>) -> ! {
+    panic!("expected the macro to {expected}, but it {branch}")
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __assert_value<T, U>(taken: &str, actual: &T, expected: &U)
+where
+    T: PartialEq<U> + Debug,
+    U: Debug,
+{
+    if actual != expected {
+        panic!("the macro {taken} `{actual:?}`, expected `{expected:?}`")
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+// Not public API. Runs the macro call in a loop in a closure, and records the branch taken
+macro_rules! __branch {
+    ($mac_call:expr) => {{
+        #[allow(unreachable_code)]
+        #[allow(clippy::diverging_sub_expression)]
+        let branch = $crate::testing::__catch(|slot| {
+            let mut first = true;
+            let broke = loop {
+                if !first {
+                    $crate::testing::__escape(slot, $crate::testing::Branch::Continued)
+                }
+                first = false;
+                let value = $mac_call;
+                $crate::testing::__escape(slot, $crate::testing::Branch::Yielded(value))
+            };
+            $crate::testing::__escape(slot, $crate::testing::Branch::Broke(broke))
+        });
+        branch
+    }};
+}
+
+/// Asserts the macro call returns, optionally with a value equal to the right hand side
+#[macro_export]
+macro_rules! assert_returns {
+    ($mac_call:expr $(,)?) => {
+        match $crate::__branch!($mac_call) {
+            $crate::testing::Branch::Returned(_) => {}
+            branch => $crate::testing::__wrong_branch("return", branch),
+        }
+    };
+    ($mac_call:expr, $value:expr $(,)?) => {
+        match $crate::__branch!($mac_call) {
+            $crate::testing::Branch::Returned(v) => {
+                $crate::testing::__assert_value("returned", &v, &$value)
+            }
+            branch => $crate::testing::__wrong_branch("return", branch),
+        }
+    };
+}
+
+/// Asserts the macro call continues the enclosing loop
+#[macro_export]
+macro_rules! assert_continues {
+    ($mac_call:expr $(,)?) => {
+        match $crate::__branch!($mac_call) {
+            $crate::testing::Branch::Continued => {}
+            branch => $crate::testing::__wrong_branch("continue", branch),
+        }
+    };
+}
+
+/// Asserts the macro call breaks the enclosing loop, optionally with a value equal to the right
+/// hand side
+#[macro_export]
+macro_rules! assert_breaks {
+    ($mac_call:expr $(,)?) => {
+        match $crate::__branch!($mac_call) {
+            $crate::testing::Branch::Broke(_) => {}
+            branch => $crate::testing::__wrong_branch("break", branch),
+        }
+    };
+    ($mac_call:expr, $value:expr $(,)?) => {
+        match $crate::__branch!($mac_call) {
+            $crate::testing::Branch::Broke(v) => {
+                $crate::testing::__assert_value("broke with", &v, &$value)
+            }
+            branch => $crate::testing::__wrong_branch("break", branch),
+        }
+    };
+}
+
+/// Asserts the macro call gives a value equal to the right hand side
+#[macro_export]
+macro_rules! assert_yields {
+    ($mac_call:expr, $value:expr $(,)?) => {
+        match $crate::__branch!($mac_call) {
+            $crate::testing::Branch::Yielded(v) => {
+                $crate::testing::__assert_value("yielded", &v, &$value)
+            }
+            branch => $crate::testing::__wrong_branch("yield a value", branch),
+        }
+    };
+}
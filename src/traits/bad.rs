@@ -44,10 +44,12 @@ macro_rules! impl_bad {
     };
 }
 
+use crate::FfiResult;
 use core::ops::ControlFlow;
 
 impl_bad!(<T, E> E Result Err);
 impl_bad!(<B, C> B ControlFlow ControlFlow::Break);
+impl_bad!(<T, E> E FfiResult FfiResult::Err);
 
 impl<T> Bad<()> for Option<T> {
     #[inline]
@@ -59,6 +61,26 @@ impl<T> Bad<()> for Option<T> {
     }
 }
 
+impl<T> Bad<()> for &Option<T> {
+    #[inline]
+    fn bad(self) -> Result<Self, ()> {
+        match self {
+            None => Err(()),
+            _ => Ok(self),
+        }
+    }
+}
+
+impl<T> Bad<()> for &mut Option<T> {
+    #[inline]
+    fn bad(self) -> Result<Self, ()> {
+        match self {
+            None => Err(()),
+            _ => Ok(self),
+        }
+    }
+}
+
 impl Bad<Self> for bool {
     fn bad(self) -> Result<Self, Self> {
         if self {
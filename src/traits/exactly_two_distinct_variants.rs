@@ -1,3 +1,4 @@
+use crate::FfiResult;
 use core::ops::ControlFlow;
 
 /// Internal marker trait for enums that have exactly one good and one bad variant
@@ -23,17 +24,22 @@ pub unsafe trait ExactlyTwoDistinctVariants {}
 
 // SAFETY: `Result` implements `Good` and `Bad`, and has exactly 2 variants
 unsafe impl<T, E> ExactlyTwoDistinctVariants for Result<T, E> {}
-unsafe impl<'a, T, E> ExactlyTwoDistinctVariants for &'a Result<T, E> {}
-unsafe impl<'a, T, E> ExactlyTwoDistinctVariants for &'a mut Result<T, E> {}
+unsafe impl<T, E> ExactlyTwoDistinctVariants for &Result<T, E> {}
+unsafe impl<T, E> ExactlyTwoDistinctVariants for &mut Result<T, E> {}
 
 // SAFETY: `Option` implements `Good` and `Bad`, and has exactly 2 variants
 unsafe impl<T> ExactlyTwoDistinctVariants for Option<T> {}
-unsafe impl<'a, T> ExactlyTwoDistinctVariants for &'a Option<T> {}
-unsafe impl<'a, T> ExactlyTwoDistinctVariants for &'a mut Option<T> {}
+unsafe impl<T> ExactlyTwoDistinctVariants for &Option<T> {}
+unsafe impl<T> ExactlyTwoDistinctVariants for &mut Option<T> {}
 
 // SAFETY: `ControlFlow` implements `Good` and `Bad`, and has exactly 2 variants
 unsafe impl<B, C> ExactlyTwoDistinctVariants for ControlFlow<B, C> {}
-unsafe impl<'a, B, C> ExactlyTwoDistinctVariants for &'a ControlFlow<B, C> {}
-unsafe impl<'a, B, C> ExactlyTwoDistinctVariants for &'a mut ControlFlow<B, C> {}
+unsafe impl<B, C> ExactlyTwoDistinctVariants for &ControlFlow<B, C> {}
+unsafe impl<B, C> ExactlyTwoDistinctVariants for &mut ControlFlow<B, C> {}
+
+// SAFETY: `FfiResult` implements `Good` and `Bad`, and has exactly 2 variants
+unsafe impl<T, E> ExactlyTwoDistinctVariants for FfiResult<T, E> {}
+unsafe impl<T, E> ExactlyTwoDistinctVariants for &FfiResult<T, E> {}
+unsafe impl<T, E> ExactlyTwoDistinctVariants for &mut FfiResult<T, E> {}
 
 unsafe impl ExactlyTwoDistinctVariants for bool {}
@@ -44,11 +44,13 @@ macro_rules! impl_good {
     };
 }
 
+use crate::FfiResult;
 use core::ops::ControlFlow;
 
 impl_good!(<T, E> T Result Ok);
 impl_good!(<T> T Option Some);
 impl_good!(<B, C> C ControlFlow ControlFlow::Continue);
+impl_good!(<T, E> T FfiResult FfiResult::Ok);
 
 impl Good<Self> for bool {
     fn good(self) -> Result<Self, Self> {
@@ -1,9 +1,13 @@
 mod bad;
 mod exactly_two_distinct_variants;
 mod good;
+mod recover;
+mod retryable;
 mod two_states;
 
 pub use bad::*;
 pub use exactly_two_distinct_variants::*;
 pub use good::*;
+pub use recover::*;
+pub use retryable::*;
 pub use two_states::*;
@@ -0,0 +1,15 @@
+/// Takes the good value back out of a failure that still holds it, used by `good!(x; recover)`
+///
+/// With the `std` feature, the `Result` of locking a `Mutex` or `RwLock` recovers the guard from
+/// its `PoisonError`.
+pub trait Recover<T> {
+    fn recover(self) -> T;
+}
+
+#[cfg(feature = "std")]
+impl<T> Recover<T> for Result<T, std::sync::PoisonError<T>> {
+    #[inline]
+    fn recover(self) -> T {
+        self.unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,43 @@
+/// Classifies values worth evaluating again, used by [`retry!`]
+///
+/// `Result<T, E>` is retryable when its error is. Derive it on an enum by marking the retryable
+/// variants with `#[bad(retryable)]`. With the `std` feature, `io::ErrorKind::Interrupted` and
+/// `io::ErrorKind::WouldBlock` are retryable.
+///
+/// [`retry!`]: crate::retry
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+}
+
+impl<T, E: Retryable> Retryable for Result<T, E> {
+    #[inline]
+    fn is_retryable(&self) -> bool {
+        matches!(self, Err(e) if e.is_retryable())
+    }
+}
+
+impl<T: Retryable + ?Sized> Retryable for &T {
+    #[inline]
+    fn is_retryable(&self) -> bool {
+        T::is_retryable(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Retryable for std::io::ErrorKind {
+    #[inline]
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            std::io::ErrorKind::Interrupted | std::io::ErrorKind::WouldBlock
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Retryable for std::io::Error {
+    #[inline]
+    fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+}
@@ -0,0 +1,7 @@
+use propagate::bad;
+use std::sync::Mutex;
+
+fn main() {
+    let lock = Mutex::new(0);
+    let _error = bad!(lock.lock(); recover);
+}
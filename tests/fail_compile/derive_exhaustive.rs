@@ -0,0 +1,34 @@
+use propagate::Propagate;
+
+#[derive(Propagate)]
+#[propagate(exhaustive)]
+enum Packet {
+    #[good]
+    Data(Vec<u8>),
+    #[bad]
+    Corrupt(u32),
+    KeepAlive,
+    #[neutral]
+    Padding(usize),
+    Unknown(u8),
+}
+
+#[derive(Propagate)]
+enum Frame {
+    #[good]
+    #[neutral]
+    Data(Vec<u8>),
+    #[bad]
+    Corrupt(u32),
+}
+
+#[derive(Propagate)]
+#[propagate(strict)]
+enum Status {
+    #[good]
+    Up,
+    #[bad]
+    Down,
+}
+
+fn main() {}
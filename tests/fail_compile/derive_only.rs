@@ -0,0 +1,30 @@
+use propagate::Propagate;
+
+#[derive(Propagate)]
+#[propagate(only(good, mut))]
+enum Unknown {
+    #[good]
+    Value(u32),
+    #[bad]
+    Error,
+}
+
+#[derive(Propagate)]
+#[propagate(only(borrow, from_good))]
+enum BorrowAlone {
+    #[good]
+    Value(u32),
+    #[bad]
+    Error,
+}
+
+#[derive(Propagate)]
+#[propagate(only(good), only(bad))]
+enum Twice {
+    #[good]
+    Value(u32),
+    #[bad]
+    Error,
+}
+
+fn main() {}
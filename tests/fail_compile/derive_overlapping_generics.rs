@@ -0,0 +1,19 @@
+use propagate::Propagate;
+
+#[derive(Propagate)]
+enum Either<T, U> {
+    #[good]
+    Left(T),
+    #[good]
+    Right(U),
+}
+
+#[derive(Propagate)]
+enum Nested<T, U> {
+    #[bad]
+    Items(Vec<T>),
+    #[bad]
+    Pairs(Vec<(U, &'static str)>),
+}
+
+fn main() {}
@@ -3,4 +3,14 @@ use propagate::Propagate;
 #[derive(Propagate)]
 struct MyStruct;
 
+#[derive(Propagate)]
+#[propagate(good_if = |_: &Missing| true, good = body)]
+struct Missing {
+    code: i32,
+}
+
+#[derive(Propagate)]
+#[propagate(good_if = |_: &Unknown| true, exhaustive)]
+struct Unknown(u8);
+
 fn main() {}
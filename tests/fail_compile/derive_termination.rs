@@ -0,0 +1,29 @@
+use propagate::Propagate;
+
+#[derive(Debug, Propagate)]
+enum Status {
+    #[good]
+    Done,
+    #[bad(exit_code = 2)]
+    Failed,
+}
+
+#[derive(Debug, Propagate)]
+#[propagate(termination(json))]
+enum Report {
+    #[good]
+    Done,
+    #[bad]
+    Failed,
+}
+
+#[derive(Propagate)]
+#[propagate(termination)]
+enum Undebuggable {
+    #[good]
+    Done,
+    #[bad]
+    Failed,
+}
+
+fn main() {}
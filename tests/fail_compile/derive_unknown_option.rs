@@ -0,0 +1,19 @@
+use propagate::Propagate;
+
+#[derive(Propagate)]
+enum Read {
+    #[good]
+    Byte(u8),
+    #[bad(retry)]
+    WouldBlock,
+}
+
+#[derive(Propagate)]
+enum Write {
+    #[good(retryable)]
+    Done,
+    #[bad]
+    Failed,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use propagate::first_good;
+
+fn port(env: Option<u16>, file: Option<u16>) -> u16 {
+    first_good!(env, file => full |_| 0)
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+#[propagate::function(strict)]
+fn parse(s: &str) -> Option<i32> {
+    s.parse::<i32>().ok()?
+}
+
+fn main() {}
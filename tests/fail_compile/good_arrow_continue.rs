@@ -0,0 +1,11 @@
+use propagate::good;
+
+fn sum(results: &[Result<i32, &str>]) -> i32 {
+    let mut sum = 0;
+    for res in results {
+        sum += good!(res => continue |err| err);
+    }
+    sum
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use propagate::good;
+
+fn parse(res: Result<i32, &str>) -> i32 {
+    good!(res => els |err: &str| err.len() as i32)
+}
+
+fn log(res: Result<i32, &str>) -> Result<i32, &str> {
+    good!(res => inspct |err: &&str| eprintln!("{err}"));
+    res
+}
+
+fn main() {}
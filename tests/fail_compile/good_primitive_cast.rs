@@ -0,0 +1,19 @@
+use propagate::{bad, good};
+
+fn widen(n: Option<u8>) -> Option<u32> {
+    Some(good!(n as u32; None))
+}
+
+fn code(res: Result<(), i64>) -> i32 {
+    bad!(res as i32; 0)
+}
+
+fn words(bytes: Option<*const u8>) -> Option<*const u16> {
+    Some(good!(bytes as *const u16; None))
+}
+
+fn main() {
+    let _ = widen(Some(1));
+    let _ = code(Err(1));
+    let _ = words(None);
+}
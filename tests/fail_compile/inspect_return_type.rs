@@ -0,0 +1,9 @@
+use propagate::good;
+
+// `inspect` returns the original enum, like `good!(res)`, and doesn't convert it
+fn len(res: Result<String, u16>) -> Result<usize, u16> {
+    let body = good!(res => inspect |status: &u16| eprintln!("{status}"));
+    Ok(body.len())
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use propagate::match_good;
+
+fn parse(s: &str) -> i32 {
+    match_good!(s.parse::<i32>() {
+        good(n) => n,
+    })
+}
+
+fn main() {}
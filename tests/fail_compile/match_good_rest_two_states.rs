@@ -0,0 +1,27 @@
+use propagate::{match_good, Propagate};
+
+#[derive(Propagate)]
+enum Switch {
+    #[good]
+    On(u8),
+    #[bad]
+    Off(u8),
+}
+
+fn level(switch: Switch) -> u8 {
+    match_good!(switch {
+        good(level) => level,
+        bad(_) => 1,
+        rest(_) => 0,
+    })
+}
+
+fn parsed(res: &Result<u8, u8>) -> u8 {
+    match_good!(res {
+        good(n) => *n,
+        bad(_) => 1,
+        rest(_) => 0,
+    })
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use propagate::{reject, reject_bad};
+
+enum Msg {
+    Text(String),
+    Ping,
+}
+
+fn ping(msg: Msg) -> Result<Msg, String> {
+    let msg = reject!(msg, Msg::Text[v] => els |v| Ok(Msg::Text(v)));
+    Ok(msg)
+}
+
+fn parse(res: Result<u32, String>) -> Result<u32, String> {
+    let res = reject_bad!(res => brek |e| Err(e));
+    res
+}
+
+fn main() {
+    let _ = ping(Msg::Ping);
+    let _ = parse(Ok(1));
+}
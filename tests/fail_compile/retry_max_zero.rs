@@ -0,0 +1,7 @@
+use propagate::retry;
+
+fn parse(s: &str) -> Result<i32, std::num::ParseIntError> {
+    Ok(retry!(s.parse::<i32>(); max = 0, when = |r| r.is_err()))
+}
+
+fn main() {}
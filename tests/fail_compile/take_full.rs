@@ -0,0 +1,15 @@
+use propagate::take;
+
+enum Msg {
+    Text(String),
+    Ping,
+}
+
+fn text(msg: Msg) -> Option<String> {
+    let text = take!(msg, Msg::Text[v] => full |_| None);
+    Some(text)
+}
+
+fn main() {
+    let _ = text(Msg::Ping);
+}
@@ -52,9 +52,8 @@ fn get_success_msg_len_or_format_str(log_data: &LogData) -> Result<usize, String
 }
 
 fn reset_error_code_on_error(log_data: &mut LogData) {
-    let error_code: &mut u32 = bad!(log_data;); // Must annotate type here
-                                                // let error_code = bad!(&mut log_data; ());
-                                                // ^^^^ FAIL: Cannot infer type because we have two #[bad] variants!
+    // Two `#[bad]` variants, `as` picks the `Bad<&mut u32>` impl
+    let error_code = bad!(log_data as &mut u32;);
     *error_code = 0;
 }
 
@@ -0,0 +1,92 @@
+//! The macros and derives of `propagate`, expanded in a `no_std` crate
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::ops::ControlFlow;
+use propagate::{attempt, bad, first_good, good, match_good, retry, Bad, FfiResult, Propagate};
+
+#[derive(Debug, PartialEq, Propagate)]
+pub enum Lookup {
+    #[good]
+    Found(u32),
+    #[bad]
+    Missing,
+}
+
+pub fn sum(values: &[Option<u32>]) -> Option<u32> {
+    let mut sum = 0;
+    for value in values {
+        sum += good!(*value);
+    }
+    Some(sum)
+}
+
+pub fn found(lookup: Lookup) -> Result<u32, &'static str> {
+    Ok(good!(lookup; Err("missing")))
+}
+
+pub fn error_code(res: Result<u32, u8>) -> Option<u8> {
+    Some(bad!(res => |_| None))
+}
+
+pub fn first_digit(chars: &[char]) -> ControlFlow<(), u32> {
+    for c in chars {
+        let digit = good!(c.to_digit(10); continue);
+        return ControlFlow::Continue(digit);
+    }
+    ControlFlow::Break(())
+}
+
+pub fn checked(res: Result<u32, &str>) -> u32 {
+    good!(res; expect "checked")
+}
+
+pub fn added(a: Option<u32>, b: Lookup) -> Option<u32> {
+    attempt! {
+        good!(a => Bad) + good!(b => Bad)
+    }
+}
+
+pub fn either(a: Option<u32>, b: Result<u32, u8>) -> u32 {
+    first_good!(a, b; else 0)
+}
+
+pub fn describe(lookup: Lookup) -> u32 {
+    match_good!(lookup {
+        good(n) => n,
+        bad(_) => 0,
+    })
+}
+
+pub fn flaky(failures: &mut u32) -> Result<u32, u32> {
+    let mut attempt = || match *failures {
+        0 => Ok(1),
+        _ => {
+            *failures -= 1;
+            Err(*failures)
+        }
+    };
+    let value = retry!(attempt(); max = 3, when = Result::is_err);
+    Ok(value)
+}
+
+pub extern "C" fn ffi_half(value: u32) -> FfiResult<u32, ()> {
+    let even = good!(value.is_multiple_of(2).then_some(value) => |_| FfiResult::Err(()));
+    FfiResult::Ok(even / 2)
+}
+
+pub fn read(ptr: *const u32) -> Option<u32> {
+    let ptr = good!(ptr => |_| None);
+    Some(unsafe { *ptr.as_ptr() })
+}
+
+#[cfg(feature = "alloc")]
+pub type BoxedError = alloc::boxed::Box<dyn core::error::Error + Send + Sync>;
+
+#[cfg(feature = "alloc")]
+pub fn boxed(res: Result<u32, &'static str>) -> Result<u32, BoxedError> {
+    Ok(good!(res => boxed))
+}
@@ -0,0 +1,51 @@
+use core::ops::ControlFlow;
+use propagate_no_std_tests::*;
+
+#[test]
+fn test_propagation() {
+    assert_eq!(sum(&[Some(1), Some(2)]), Some(3));
+    assert_eq!(sum(&[Some(1), None]), None);
+    assert_eq!(found(Lookup::Found(1)), Ok(1));
+    assert_eq!(found(Lookup::Missing), Err("missing"));
+    assert_eq!(error_code(Err(3)), Some(3));
+    assert_eq!(error_code(Ok(3)), None);
+    assert_eq!(first_digit(&['x', '7']), ControlFlow::Continue(7));
+    assert_eq!(first_digit(&['x']), ControlFlow::Break(()));
+}
+
+#[test]
+fn test_blocks() {
+    assert_eq!(added(Some(1), Lookup::Found(2)), Some(3));
+    assert_eq!(added(Some(1), Lookup::Missing), None);
+    assert_eq!(either(None, Ok(2)), 2);
+    assert_eq!(either(None, Err(1)), 0);
+    assert_eq!(describe(Lookup::Found(2)), 2);
+    assert_eq!(describe(Lookup::Missing), 0);
+}
+
+#[test]
+fn test_retry() {
+    assert_eq!(flaky(&mut 2), Ok(1));
+    assert_eq!(flaky(&mut 5), Err(2));
+}
+
+#[test]
+fn test_pointers_and_ffi() {
+    assert_eq!(read(&4), Some(4));
+    assert_eq!(read(core::ptr::null()), None);
+    assert_eq!(ffi_half(4), propagate::FfiResult::Ok(2));
+    assert_eq!(ffi_half(3), propagate::FfiResult::Err(()));
+}
+
+#[test]
+#[should_panic(expected = "checked: \"failed\"")]
+fn test_expect() {
+    checked(Err("failed"));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_boxed() {
+    assert_eq!(boxed(Ok(1)).unwrap(), 1);
+    assert_eq!(boxed(Err("failed")).unwrap_err().to_string(), "failed");
+}
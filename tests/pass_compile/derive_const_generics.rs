@@ -0,0 +1,19 @@
+use propagate::{good, Propagate};
+
+#[derive(Propagate)]
+enum Frame<const N: usize> {
+    #[good]
+    Full([u8; N]),
+    #[bad]
+    Partial(usize),
+}
+
+fn checksum<const N: usize>(frame: &Frame<N>) -> u32 {
+    let bytes: &[u8; N] = good!(frame; 0);
+    bytes.iter().map(|&byte| byte as u32).sum()
+}
+
+fn main() {
+    assert_eq!(checksum(&Frame::Full([1, 2, 3])), 6);
+    assert_eq!(checksum(&Frame::<3>::Partial(2)), 0);
+}
@@ -0,0 +1,22 @@
+use propagate::{good, Propagate};
+
+#[derive(Propagate)]
+enum Loaded<T: Clone, E>
+where
+    E: core::fmt::Debug,
+{
+    #[good]
+    Value(T),
+    #[bad]
+    Failure(E),
+}
+
+fn cloned<T: Clone, E: core::fmt::Debug>(loaded: &Loaded<T, E>) -> Option<T> {
+    let value: &T = good!(loaded; None);
+    Some(value.clone())
+}
+
+fn main() {
+    let loaded: Loaded<i32, &str> = Loaded::Value(1);
+    assert_eq!(cloned(&loaded), Some(1));
+}
@@ -0,0 +1,39 @@
+use propagate::{good, Propagate};
+
+// `T` never equals a type containing it, so these impls don't overlap
+#[derive(Propagate)]
+enum Batch<T> {
+    #[good]
+    One(T),
+    #[good]
+    Many(Vec<T>),
+    #[good]
+    Maybe(Option<T>),
+    #[bad]
+    Failed(u8),
+}
+
+#[derive(Propagate)]
+enum Pair<T, U> {
+    #[good]
+    Left(T, Vec<T>),
+    #[good]
+    Right(Vec<U>, U),
+    #[bad]
+    Failed,
+}
+
+fn many(batch: Batch<u32>) -> Option<usize> {
+    let many: Vec<u32> = good!(batch; None);
+    Some(many.len())
+}
+
+fn main() {
+    assert_eq!(many(Batch::Many(vec![1, 2])), Some(2));
+    assert_eq!(many(Batch::One(1)), None);
+    let left = |pair: Pair<u8, u16>| -> Option<usize> {
+        let (_, rest): (u8, Vec<u8>) = good!(pair; None);
+        Some(rest.len())
+    };
+    assert_eq!(left(Pair::Left(1, vec![2])), Some(1));
+}
@@ -0,0 +1,18 @@
+use propagate::{bad, Propagate};
+
+#[derive(Propagate)]
+enum Reply<T = String> {
+    #[good]
+    Body(T),
+    #[bad]
+    Status(u16),
+}
+
+fn status(reply: Reply) -> u16 {
+    bad!(reply; 200)
+}
+
+fn main() {
+    assert_eq!(status(Reply::Status(404)), 404);
+    assert_eq!(status(Reply::Body("ok".to_owned())), 200);
+}
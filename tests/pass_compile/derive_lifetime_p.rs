@@ -0,0 +1,18 @@
+use propagate::{good, Propagate};
+
+#[derive(Propagate)]
+enum Token<'p, 'p0> {
+    #[good]
+    Word(&'p str),
+    #[bad]
+    Garbage(&'p0 [u8]),
+}
+
+fn word<'a>(token: &'a Token<'_, '_>) -> &'a str {
+    good!(token; "")
+}
+
+fn main() {
+    assert_eq!(word(&Token::Word("hello")), "hello");
+    assert_eq!(word(&Token::Garbage(b"\xff")), "");
+}
@@ -0,0 +1,130 @@
+use propagate::{attempt, bad, good, Propagate};
+
+#[test]
+fn test_tail_is_wrapped() {
+    let res: Result<i32, &str> = attempt! { 1 + 2 };
+    assert_eq!(res, Ok(3));
+
+    let res: Option<()> = attempt! {
+        let _ = 1;
+    };
+    assert_eq!(res, Some(()));
+}
+
+#[test]
+fn test_good_breaks_out_of_the_block() {
+    let mut reached = false;
+    let res: Result<i32, &str> = attempt! {
+        let a = good!(Ok::<i32, &str>(1));
+        let b = good!(Err::<i32, &str>("nope"));
+        reached = true;
+        a + b
+    };
+    assert_eq!(res, Err("nope"));
+    assert!(!reached);
+}
+
+#[test]
+fn test_bad_breaks_out_of_the_block() {
+    let res: Result<i32, i32> = attempt! {
+        let e = bad!(Err::<i32, i32>(1));
+        bad!(Ok::<i32, i32>(5));
+        e
+    };
+    assert_eq!(res, Ok(5));
+}
+
+#[test]
+fn test_converts_through_from_bad() {
+    let res: Option<i32> = attempt! {
+        good!("x".parse::<i32>().ok()) + 1
+    };
+    assert_eq!(res, None);
+}
+
+#[test]
+fn test_nested_expressions_and_macros() {
+    let res: Result<String, &str> = attempt! {
+        let nested: Result<Result<i32, &str>, &str> = Ok(Ok(5));
+        assert_eq!(good!(good!(nested)), 5);
+        format!("{}", good!(Err::<i32, &str>("inner")))
+    };
+    assert_eq!(res, Err("inner"));
+}
+
+#[test]
+fn test_clauses_keep_their_meaning() {
+    let mut sum = 0;
+    let res: Option<i32> = attempt! {
+        for s in ["1", "x", "2"] {
+            sum += good!(s.parse::<i32>(); continue);
+        }
+        let doubled = good!(Err::<i32, i32>(4) => else |e| e * 2);
+        sum + doubled
+    };
+    assert_eq!(res, Some(11));
+}
+
+fn returns_from_closure() -> Result<i32, i32> {
+    let res: Option<i32> = attempt! {
+        let closure = || -> Result<i32, i32> {
+            good!(Err::<i32, i32>(7));
+            Ok(0)
+        };
+        good!(closure().ok())
+    };
+    Ok(res.unwrap_or(-1))
+}
+
+#[test]
+fn test_closures_are_left_alone() {
+    assert_eq!(returns_from_closure(), Ok(-1));
+}
+
+fn returns_from_function(res: Result<i32, i32>) -> i32 {
+    let inner: Option<i32> = attempt! {
+        if res.is_err() {
+            return -1;
+        }
+        good!(res.ok())
+    };
+    inner.unwrap()
+}
+
+#[test]
+fn test_return_leaves_the_function() {
+    assert_eq!(returns_from_function(Ok(3)), 3);
+    assert_eq!(returns_from_function(Err(3)), -1);
+}
+
+#[test]
+fn test_nested_attempt() {
+    let outer: Result<Option<i32>, &str> = attempt! {
+        let inner: Option<i32> = attempt! {
+            good!(None::<i32>)
+        };
+        good!(Ok::<(), &str>(()));
+        inner
+    };
+    assert_eq!(outer, Ok(None));
+}
+
+#[derive(Propagate, Debug, PartialEq)]
+enum Fetch {
+    #[good]
+    Fetched(u32),
+    #[bad]
+    Missing(&'static str),
+}
+
+#[test]
+fn test_derived_enum() {
+    let fetch = |n: u32| match n {
+        0 => Fetch::Missing("zero"),
+        n => Fetch::Fetched(n),
+    };
+    let res: Fetch = attempt! { good!(fetch(1)) + good!(fetch(2)) };
+    assert_eq!(res, Fetch::Fetched(3));
+    let res: Fetch = attempt! { good!(fetch(1)) + good!(fetch(0)) };
+    assert_eq!(res, Fetch::Missing("zero"));
+}
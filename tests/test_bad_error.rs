@@ -0,0 +1,102 @@
+#[cfg(feature = "alloc")]
+use propagate::good;
+use propagate::{BadError, Propagate};
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "alloc")]
+type BoxedError = Box<dyn Error + Send + Sync>;
+
+#[derive(Debug)]
+struct Inner;
+
+impl fmt::Display for Inner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "inner")
+    }
+}
+
+impl Error for Inner {}
+
+#[derive(Debug)]
+struct Outer(Inner);
+
+impl fmt::Display for Outer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "outer")
+    }
+}
+
+impl Error for Outer {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[derive(Debug, Propagate)]
+enum Fetch {
+    #[good]
+    Body(String),
+    #[bad]
+    Status(u16),
+}
+
+#[test]
+fn test_bad_error_display() {
+    let error = BadError::new(404u16);
+    assert_eq!(error.to_string(), "404");
+    assert_eq!(format!("{error:?}"), "404");
+    assert!(error.source().is_none());
+    assert_eq!(error.into_inner(), 404);
+}
+
+#[test]
+fn test_bad_error_source() {
+    let error = BadError::from_error(Outer(Inner));
+    assert_eq!(error.to_string(), "outer");
+    assert_eq!(error.source().unwrap().to_string(), "inner");
+    assert_eq!(error.get().0.to_string(), "inner");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_boxed_display_payload() {
+    let fetch = |fetch: Fetch| -> Result<String, BoxedError> { Ok(good!(fetch => boxed)) };
+    assert_eq!(fetch(Fetch::Body("ok".to_owned())).unwrap(), "ok");
+    let error = fetch(Fetch::Status(500)).unwrap_err();
+    assert_eq!(error.to_string(), "500");
+    assert!(error.downcast_ref::<BadError<u16>>().is_some());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_boxed_error_payload() {
+    let parse = |s: &str| -> Result<i32, BoxedError> { Ok(good!(s.parse::<i32>() => boxed)) };
+    assert_eq!(parse("7").unwrap(), 7);
+    let error = parse("x").unwrap_err();
+    // An error payload is boxed as is
+    assert!(error.downcast_ref::<std::num::ParseIntError>().is_some());
+
+    let outer = |result: Result<(), Outer>| -> Result<(), BoxedError> {
+        good!(result => boxed);
+        Ok(())
+    };
+    let error = outer(Err(Outer(Inner))).unwrap_err();
+    assert_eq!(error.source().unwrap().to_string(), "inner");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[allow(unused_variables)]
+fn test_boxed_is_a_keyword_next_to_a_local() {
+    let boxed = |e: std::num::ParseIntError| -> Result<i32, BoxedError> {
+        Err(format!("local: {e}").into())
+    };
+    let parse = |s: &str| -> Result<i32, BoxedError> { Ok(good!(s.parse::<i32>() => boxed)) };
+    let error = parse("x").unwrap_err();
+    assert!(error.downcast_ref::<std::num::ParseIntError>().is_some());
+
+    let parse = |s: &str| -> Result<i32, BoxedError> { Ok(good!(s.parse::<i32>() => (boxed))) };
+    let error = parse("x").unwrap_err();
+    assert_eq!(error.to_string(), "local: invalid digit found in string");
+}
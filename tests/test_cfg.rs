@@ -0,0 +1,82 @@
+use propagate::{bad, good, is_bad, is_good, FromBad, FromGood, Propagate};
+mod util;
+
+// `cfg(not(test))` and `cfg(test)` stand in for a feature turned off and on
+#[derive(Debug, PartialEq, Propagate)]
+#[allow(dead_code)]
+enum Packet {
+    #[cfg(not(test))]
+    #[good]
+    Compressed(Vec<u8>),
+    #[good]
+    Plain(String),
+    #[cfg(test)]
+    Ping,
+    #[bad]
+    Malformed(u32),
+    #[cfg(not(test))]
+    #[bad]
+    Legacy(u32),
+}
+
+#[derive(Debug, PartialEq, Propagate)]
+enum Switch {
+    #[good]
+    High(u8),
+    #[cfg(not(test))]
+    Floating,
+    #[bad]
+    Low(u8),
+}
+
+#[derive(Debug, PartialEq, Propagate)]
+#[allow(dead_code)]
+enum Tristate {
+    #[good]
+    High(u8),
+    #[cfg(test)]
+    Floating,
+    #[bad]
+    Low(u8),
+}
+
+#[test]
+fn configured_out_variants_keep_indexes() {
+    assert!(is_good!(Packet::Plain("data".to_owned())));
+    assert!(!is_bad!(Packet::Plain("data".to_owned())));
+    assert!(!is_good!(Packet::Ping));
+    assert!(!is_bad!(Packet::Ping));
+    assert!(is_bad!(Packet::Malformed(3)));
+    assert!(!is_good!(Packet::Malformed(3)));
+}
+
+#[test]
+fn configured_out_variants_are_not_matched() {
+    let packet = Packet::Plain("data".to_owned());
+    assert_unwrap_eq!(good!(&packet), &"data".to_owned());
+    let packet = Packet::Malformed(3);
+    assert_unwrap_eq!(bad!(packet), 3);
+    let packet = Packet::Ping;
+    assert_short_circuit_eq!(bad!(packet), u32, Packet::Ping);
+}
+
+#[test]
+fn overload_configured_out_keeps_from_bad() {
+    // `Legacy(u32)` is configured out, so `Malformed` is the only `#[bad]` `u32`
+    assert_eq!(Packet::from_bad(7), Packet::Malformed(7));
+    assert_eq!(Packet::from_good("data".to_owned()), Packet::Plain("data".to_owned()));
+}
+
+#[test]
+fn two_states_when_extra_variant_configured_out() {
+    let switch = Switch::Low(2);
+    assert_short_circuit_eq!(good!(switch => |port| port * 2), 4);
+    let switch = Switch::High(1);
+    assert_unwrap_eq!(good!(switch => |port| port * 2), 1);
+    // `Tristate` keeps its `Floating` variant, so it needs `=> full`
+    let tristate = Tristate::Floating;
+    assert_short_circuit_eq!(
+        good!(tristate => full |tristate| tristate),
+        Tristate::Floating
+    );
+}
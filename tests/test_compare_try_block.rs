@@ -1,6 +1,6 @@
 #![feature(try_blocks)]
 
-use propagate::{good, Bad, Good};
+use propagate::{attempt, good};
 use std::num::ParseIntError;
 
 fn add_try_blocks(str1: &str, str2: &str) -> i8 {
@@ -18,17 +18,28 @@ fn add_propagate(str1: &str, str2: &str) -> i8 {
     int1 + int2
 }
 
+fn add_attempt(str1: &str, str2: &str) -> i8 {
+    let result: Result<_, ParseIntError> = attempt! {
+        let int1 = str1.parse::<i8>();
+        let int2 = str2.parse::<i8>();
+        good!(int1) + good!(int2)
+    };
+    result.unwrap_or(-1)
+}
+
 #[test]
 fn compare_add() {
     let a = "12";
     let b = "21";
     assert_eq!(add_try_blocks(a, b), 33);
     assert_eq!(add_propagate(a, b), 33);
+    assert_eq!(add_attempt(a, b), 33);
 
     let c = "abc";
     let d = "123";
     assert_eq!(add_try_blocks(c, d), -1);
     assert_eq!(add_propagate(c, d), -1);
+    assert_eq!(add_attempt(c, d), -1);
 }
 
 fn multiple_results_try_block(
@@ -53,12 +64,11 @@ fn multiple_results_propagate(
     res2: Result<i32, i32>,
     res3: Result<i32, i32>,
 ) {
-    let res: Result<_, i32> = (|| {
-        good!(res1 => Bad);
-        good!(res2 => Bad);
-        good!(res3 => Bad);
-        Good(())
-    })();
+    let res: Result<_, i32> = attempt! {
+        good!(res1);
+        good!(res2);
+        good!(res3);
+    };
     if let Err(_err) = res {
         stderr.push_str("failure");
     }
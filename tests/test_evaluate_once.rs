@@ -0,0 +1,205 @@
+use propagate::{bad, good, reject, reject_bad, reject_good, take, Propagate};
+use std::cell::Cell;
+
+#[derive(Debug, PartialEq, Propagate)]
+#[allow(dead_code)]
+enum Msg {
+    #[good]
+    Text(String),
+    Ping,
+    Pair(String, String),
+    #[bad]
+    Error(String),
+}
+
+/// Hands out messages and counts how many times it was asked
+struct Inbox {
+    calls: Cell<u32>,
+    msg: fn() -> Msg,
+}
+
+impl Inbox {
+    fn new(msg: fn() -> Msg) -> Self {
+        Inbox {
+            calls: Cell::new(0),
+            msg,
+        }
+    }
+
+    fn next_msg(&self) -> Msg {
+        self.calls.set(self.calls.get() + 1);
+        (self.msg)()
+    }
+}
+
+fn counted<'a, T>(borrows: &Cell<u32>, value: &'a T) -> &'a T {
+    borrows.set(borrows.get() + 1);
+    value
+}
+
+fn counted_mut<'a, T>(borrows: &Cell<u32>, value: &'a mut T) -> &'a mut T {
+    borrows.set(borrows.get() + 1);
+    value
+}
+
+fn text() -> Msg {
+    Msg::Text("hello".to_owned())
+}
+
+fn error() -> Msg {
+    Msg::Error("oops".to_owned())
+}
+
+fn ping() -> Msg {
+    Msg::Ping
+}
+
+#[test]
+fn take_evaluates_once() {
+    let inbox = Inbox::new(text);
+    let taken = (|| Some(take!(inbox.next_msg(), Msg::Text[v]; None)))();
+    assert_eq!(taken, Some("hello".to_owned()));
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(error);
+    let msg = (|| {
+        take!(inbox.next_msg(), Msg::Text[v]);
+        unreachable!()
+    })();
+    assert_eq!(msg, Msg::Error("oops".to_owned()));
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(ping);
+    let msg = (|| {
+        take!(inbox.next_msg(), Msg::Ping[] => |msg| msg);
+        Msg::Text("unreachable".to_owned())
+    })();
+    assert_eq!(msg, Msg::Text("unreachable".to_owned()));
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(text);
+    let pair = (|| Some(take!(inbox.next_msg(), Msg::Pair[a, b]; None)))();
+    assert_eq!(pair, None);
+    assert_eq!(inbox.calls.get(), 1);
+}
+
+#[test]
+fn take_evaluates_borrows_once() {
+    let inbox = Inbox::new(text);
+    let mut msg = inbox.next_msg();
+    let borrows = Cell::new(0);
+    fn take_text_mut<'a>(borrows: &Cell<u32>, msg: &'a mut Msg) -> Option<&'a mut String> {
+        Some(take!(counted_mut(borrows, msg), Msg::Text[v]; None))
+    }
+    take_text_mut(&borrows, &mut msg).unwrap().push('!');
+    assert_eq!(borrows.get(), 1);
+    assert_eq!(msg, Msg::Text("hello!".to_owned()));
+
+    let borrows = Cell::new(0);
+    let rest: &Msg = (|| {
+        take!(counted(&borrows, &msg), Msg::Error[v]);
+        unreachable!()
+    })();
+    assert_eq!(rest, &Msg::Text("hello!".to_owned()));
+    assert_eq!(borrows.get(), 1);
+}
+
+#[test]
+fn reject_evaluates_once() {
+    let inbox = Inbox::new(text);
+    let msg = (|| Some(reject!(inbox.next_msg(), Msg::Error[v]; None)))();
+    assert_eq!(msg, Some(Msg::Text("hello".to_owned())));
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(error);
+    let err = (|| {
+        reject!(inbox.next_msg(), Msg::Error[v] => Err);
+        Ok(())
+    })();
+    assert_eq!(err, Err("oops".to_owned()));
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(ping);
+    let msg = (|| Some(reject!(inbox.next_msg(), Msg::Ping[]; None)))();
+    assert_eq!(msg, None);
+    assert_eq!(inbox.calls.get(), 1);
+}
+
+#[test]
+fn reject_good_evaluates_once() {
+    let inbox = Inbox::new(error);
+    let msg = (|| Some(reject_good!(inbox.next_msg(); None)))();
+    assert_eq!(msg, Some(Msg::Error("oops".to_owned())));
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(text);
+    let msg = (|| Some(reject_good!(inbox.next_msg(); None)))();
+    assert_eq!(msg, None);
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(text);
+    let rejected = (|| {
+        reject_good!(inbox.next_msg());
+        unreachable!()
+    })();
+    assert_eq!(rejected, "hello".to_owned());
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(ping);
+    let mut msg = inbox.next_msg();
+    let borrows = Cell::new(0);
+    fn reject_good_mut<'a>(borrows: &Cell<u32>, msg: &'a mut Msg) -> Option<&'a mut Msg> {
+        Some(reject_good!(counted_mut(borrows, msg); None))
+    }
+    assert_eq!(reject_good_mut(&borrows, &mut msg), Some(&mut Msg::Ping));
+    assert_eq!(borrows.get(), 1);
+}
+
+#[test]
+fn reject_bad_evaluates_once() {
+    let inbox = Inbox::new(text);
+    let msg = (|| Some(reject_bad!(inbox.next_msg(); None)))();
+    assert_eq!(msg, Some(Msg::Text("hello".to_owned())));
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(error);
+    let msg = (|| Some(reject_bad!(inbox.next_msg(); None)))();
+    assert_eq!(msg, None);
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(error);
+    let msg = (|| {
+        reject_bad!(inbox.next_msg() => |err| Some(Msg::Text(err)));
+        None
+    })();
+    assert_eq!(msg, Some(Msg::Text("oops".to_owned())));
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(ping);
+    let msg = inbox.next_msg();
+    let borrows = Cell::new(0);
+    let rest: Option<&Msg> = (|| Some(reject_bad!(counted(&borrows, &msg); None)))();
+    assert_eq!(rest, Some(&Msg::Ping));
+    assert_eq!(borrows.get(), 1);
+}
+
+#[test]
+fn good_and_bad_evaluate_once() {
+    let inbox = Inbox::new(text);
+    let good = (|| Some(good!(inbox.next_msg(); None)))();
+    assert_eq!(good, Some("hello".to_owned()));
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(ping);
+    let msg = (|| {
+        good!(inbox.next_msg() => full Some);
+        None
+    })();
+    assert_eq!(msg, Some(Msg::Ping));
+    assert_eq!(inbox.calls.get(), 1);
+
+    let inbox = Inbox::new(error);
+    let err = (|| Some(bad!(inbox.next_msg(); None)))();
+    assert_eq!(err, Some("oops".to_owned()));
+    assert_eq!(inbox.calls.get(), 1);
+}
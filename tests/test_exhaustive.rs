@@ -0,0 +1,54 @@
+use propagate::{good, is_bad, is_good, is_neutral, Propagate};
+
+#[derive(Debug, Propagate)]
+#[propagate(exhaustive)]
+#[allow(dead_code)]
+enum Packet {
+    #[good]
+    Data(Vec<u8>),
+    #[bad]
+    Corrupt(u32),
+    #[neutral]
+    KeepAlive,
+    #[propagate(skip)]
+    Padding(usize),
+}
+
+#[test]
+fn test_classification() {
+    let packets = [
+        Packet::Data(vec![1]),
+        Packet::Corrupt(2),
+        Packet::KeepAlive,
+        Packet::Padding(3),
+    ];
+    let classified: Vec<(bool, bool, bool)> = packets
+        .iter()
+        .map(|packet| (is_good!(packet), is_bad!(packet), is_neutral!(packet)))
+        .collect();
+    assert_eq!(
+        classified,
+        [
+            (true, false, false),
+            (false, true, false),
+            (false, false, true),
+            (false, false, true),
+        ]
+    );
+}
+
+#[test]
+fn test_neutral_is_propagated() {
+    let len = |packet: Packet| -> usize {
+        let data: Vec<u8> = good!(packet; return 0);
+        data.len()
+    };
+    assert_eq!(len(Packet::Data(vec![1, 2])), 2);
+    assert_eq!(len(Packet::KeepAlive), 0);
+}
+
+#[test]
+fn test_std_types_are_never_neutral() {
+    assert!(!is_neutral!(Ok::<u8, u8>(1)));
+    assert!(!is_neutral!(None::<u8>));
+}
@@ -0,0 +1,169 @@
+use propagate::{bad, good, Propagate};
+use std::cell::Cell;
+use std::panic::{self, Location};
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, Propagate)]
+#[allow(dead_code)]
+enum Config {
+    #[good]
+    Loaded(String),
+    Missing,
+    #[bad]
+    Invalid(u32),
+}
+
+#[test]
+#[should_panic(expected = "loading config: \"no such file\"")]
+fn expect_reports_bad_value() {
+    let res: Result<i32, &str> = Err("no such file");
+    let _: i32 = good!(res; expect "loading config");
+}
+
+#[test]
+#[should_panic(expected = "loading config: \"no such file\"")]
+fn expect_reports_bad_value_of_reference() {
+    let res: Result<i32, &str> = Err("no such file");
+    let _: &i32 = good!(&res; expect "loading config");
+}
+
+#[test]
+#[should_panic(expected = "loading config: Missing")]
+fn expect_reports_whole_enum() {
+    let config = Config::Missing;
+    let _: String = good!(config; expect "loading config");
+}
+
+#[test]
+#[should_panic(expected = "loading config: \"no such file\"")]
+fn expect_reports_bad_value_of_two_states() {
+    let res: Result<i32, &str> = Err("no such file");
+    let _: i32 = good!(res => expect "loading config");
+}
+
+#[test]
+#[should_panic(expected = "loading config: Missing")]
+fn expect_full_reports_whole_enum() {
+    let config = Config::Missing;
+    let _: String = good!(config => full expect "loading config");
+}
+
+#[test]
+#[should_panic(expected = "expected an error: 1")]
+fn bad_expect_reports_good_value() {
+    let res: Result<i32, &str> = Ok(1);
+    let _: &str = bad!(res; expect "expected an error");
+}
+
+#[test]
+#[should_panic(expected = "internal error: entered unreachable code: Invalid(3)")]
+fn unreachable_reports_whole_enum() {
+    let config = Config::Invalid(3);
+    let _: String = good!(config; unreachable);
+}
+
+#[test]
+#[should_panic(expected = "not yet implemented: 3")]
+fn todo_reports_bad_value() {
+    let res: Result<i32, i32> = Err(3);
+    let _: i32 = good!(res => todo);
+}
+
+#[test]
+fn panic_clauses_keep_good_value() {
+    let res: Result<i32, &str> = Ok(1);
+    assert_eq!(good!(res; expect "unreachable"), 1);
+    let config = Config::Loaded("path".to_owned());
+    assert_eq!(good!(&config => full unreachable), "path");
+    let opt = Some(2);
+    assert_eq!(good!(opt; todo), 2);
+}
+
+#[test]
+fn unreachable_macro_is_not_a_keyword() {
+    let opt = Some(2);
+    assert_eq!(good!(opt; unreachable!()), 2);
+}
+
+#[test]
+#[should_panic(expected = "not yet implemented: 3")]
+#[allow(unused_variables)]
+fn todo_is_a_keyword_next_to_a_local() {
+    let todo = 0;
+    let res: Result<i32, i32> = Err(3);
+    let _: i32 = good!(res; todo);
+}
+
+#[test]
+#[should_panic(expected = "internal error: entered unreachable code: 3")]
+#[allow(unused_variables)]
+fn unreachable_is_a_keyword_next_to_a_closure() {
+    let unreachable = |e: i32| e;
+    let res: Result<i32, i32> = Err(3);
+    let _: i32 = good!(res => unreachable);
+}
+
+#[test]
+fn parenthesized_locals_are_not_keywords() {
+    fn returned(res: Result<i32, i32>) -> i32 {
+        let todo = 0;
+        good!(res; (todo))
+    }
+    fn applied(res: Result<i32, i32>) -> i32 {
+        let unreachable = |e: i32| e + 1;
+        good!(res => (unreachable))
+    }
+    assert_eq!(returned(Err(3)), 0);
+    assert_eq!(applied(Err(3)), 4);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "debug only: \"skipped\"")]
+fn debug_expect_panics_in_debug_builds() {
+    let results: [Result<i32, &str>; 2] = [Ok(1), Err("skipped")];
+    for res in results {
+        let _: i32 = good!(res => debug_expect "debug only"; continue);
+    }
+}
+
+#[test]
+#[cfg(not(debug_assertions))]
+fn debug_expect_propagates_in_release_builds() {
+    let results: [Result<i32, &str>; 3] = [Ok(1), Err("skipped"), Ok(2)];
+    let mut sum = 0;
+    for res in results {
+        sum += good!(res => debug_expect "debug only"; continue);
+    }
+    assert_eq!(sum, 3);
+}
+
+#[test]
+fn debug_expect_keeps_good_value() {
+    let res: Result<i32, &str> = Ok(1);
+    assert_eq!(good!(res; debug_expect "debug only"; else 0), 1);
+}
+
+thread_local! {
+    static CAPTURE: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LINE: Cell<Option<u32>> = const { Cell::new(None) };
+}
+
+#[test]
+fn panic_location_is_the_call_site() {
+    // Other tests panic in parallel, so the hook only captures the panics of this thread
+    let default_hook = Arc::new(panic::take_hook());
+    let forward = Arc::clone(&default_hook);
+    panic::set_hook(Box::new(move |info| match CAPTURE.get() {
+        true => PANIC_LINE.set(info.location().map(Location::line)),
+        false => forward(info),
+    }));
+    let res: Result<i32, &str> = Err("no such file");
+    CAPTURE.set(true);
+    let expected_line = line!() + 1;
+    let caught = panic::catch_unwind(|| good!(res; expect "loading config"));
+    CAPTURE.set(false);
+    panic::set_hook(Box::new(move |info| default_hook(info)));
+    assert!(caught.is_err());
+    assert_eq!(PANIC_LINE.get(), Some(expected_line));
+}
@@ -0,0 +1,70 @@
+use core::mem::{align_of, offset_of, size_of, ManuallyDrop};
+use propagate::{bad, good, is_bad, is_good, Bad, FfiResult, Good};
+
+/// What a C header declares for `FfiResult<T, E>`
+#[repr(C)]
+struct CResult<T, E> {
+    tag: u8,
+    value: CValue<T, E>,
+}
+
+#[repr(C)]
+union CValue<T, E> {
+    ok: ManuallyDrop<T>,
+    err: ManuallyDrop<E>,
+}
+
+fn as_c<T, E>(result: &FfiResult<T, E>) -> &CResult<T, E> {
+    assert_eq!(size_of::<FfiResult<T, E>>(), size_of::<CResult<T, E>>());
+    assert_eq!(align_of::<FfiResult<T, E>>(), align_of::<CResult<T, E>>());
+    unsafe { &*(result as *const FfiResult<T, E> as *const CResult<T, E>) }
+}
+
+#[test]
+fn test_layout() {
+    assert_eq!(offset_of!(CResult<u32, u16>, tag), 0);
+    assert_eq!(offset_of!(CResult<u32, u16>, value), 4);
+    assert_eq!(size_of::<FfiResult<u32, u16>>(), 8);
+    assert_eq!(offset_of!(CResult<u8, u64>, value), 8);
+    assert_eq!(size_of::<FfiResult<u8, u64>>(), 16);
+
+    let ok = FfiResult::<u32, u16>::Ok(7);
+    let c = as_c(&ok);
+    assert_eq!(c.tag, 0);
+    assert_eq!(unsafe { *c.value.ok }, 7);
+
+    let err = FfiResult::<u8, u64>::Err(u64::MAX - 1);
+    let c = as_c(&err);
+    assert_eq!(c.tag, 1);
+    assert_eq!(unsafe { *c.value.err }, u64::MAX - 1);
+}
+
+#[test]
+fn test_conversions() {
+    for result in [Ok(1), Err("no")] {
+        let ffi = FfiResult::from(result);
+        assert_eq!(ffi.is_ok(), result.is_ok());
+        assert_eq!(Result::from(ffi), result);
+    }
+}
+
+extern "C" fn checked_div(a: u32, b: u32) -> FfiResult<u32, u8> {
+    let quotient = good!(a.checked_div(b) => |_| Bad(1));
+    Good(quotient)
+}
+
+#[test]
+fn test_propagation() {
+    assert_eq!(checked_div(6, 3), FfiResult::Ok(2));
+    assert_eq!(checked_div(6, 0), FfiResult::Err(1));
+    assert!(is_good!(checked_div(1, 1)));
+    assert!(is_bad!(checked_div(1, 0)));
+
+    let caller = |result: FfiResult<u32, u8>| -> Result<u32, u8> {
+        let value: &u32 = good!(&result => |code: &u8| Err(*code));
+        Ok(*value + 1)
+    };
+    assert_eq!(caller(checked_div(6, 3)), Ok(3));
+    assert_eq!(caller(checked_div(6, 0)), Err(1));
+    assert_eq!(bad!(checked_div(1, 0); else 0), 1);
+}
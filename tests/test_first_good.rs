@@ -0,0 +1,79 @@
+#![cfg(feature = "testing")]
+
+use propagate::testing::{assert_breaks, assert_continues, assert_returns, assert_yields};
+use propagate::{first_good, Propagate};
+use std::cell::Cell;
+
+#[derive(Propagate, Debug, PartialEq)]
+enum Source {
+    #[good]
+    Found(u32),
+    Missing,
+}
+
+#[test]
+fn test_first_good_value() {
+    assert_yields!(first_good!(None, Some(1), Some(2); else 0), 1);
+    assert_yields!(
+        first_good!(Err::<u32, &str>("e"), Source::Found(3); else 0),
+        3
+    );
+    assert_yields!(first_good!(Some(4)), 4);
+}
+
+#[test]
+fn test_lazy_evaluation() {
+    let evaluated = Cell::new(0);
+    let source = |value: Option<u32>| {
+        evaluated.set(evaluated.get() + 1);
+        value
+    };
+    assert_yields!(
+        first_good!(source(None), source(Some(1)), source(Some(2)); else 0),
+        1
+    );
+    assert_eq!(evaluated.get(), 2);
+}
+
+#[test]
+fn test_all_bad() {
+    assert_yields!(
+        first_good!(None, Err::<u32, &str>("e"), Source::Missing; else 7),
+        7
+    );
+    assert_continues!(first_good!(None::<u32>, Source::Missing; continue));
+    assert_breaks!(first_good!(None::<u32>; break 2), 2);
+    assert_returns!(
+        first_good!(None::<u32>, Err::<u32, &str>("e")),
+        ((), "e")
+    );
+}
+
+#[test]
+fn test_closures_get_every_bad_value() {
+    // `Source` isn't two-state, so its whole value is kept
+    type Bad = ((), &'static str, Source);
+    let report = |(env, file, default): Bad| format!("{env:?}, {file:?}, {default:?}");
+    assert_returns!(
+        first_good!(None, Err("no file"), Source::Missing => report),
+        "(), \"no file\", Missing"
+    );
+    assert_yields!(
+        first_good!(None, Err("no file"), Source::Missing => else |bad: Bad| u32::from(bad.2 == Source::Missing)),
+        1
+    );
+
+    let mut failures = 0;
+    assert_continues!(first_good!(None::<u32>, Source::Missing => do |_| failures += 1; continue));
+    assert_eq!(failures, 1);
+}
+
+#[test]
+fn test_by_reference() {
+    let env: Option<u32> = None;
+    let file: Result<u32, &str> = Ok(5);
+    assert_yields!(*first_good!(&env, &file; else &0), 5);
+    assert_eq!(file, Ok(5));
+    let file: Result<u32, &str> = Err("no file");
+    assert_returns!(first_good!(&env, &file), ((), &"no file"));
+}
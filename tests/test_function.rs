@@ -0,0 +1,114 @@
+use propagate::Propagate;
+use std::ops::ControlFlow;
+
+#[derive(Propagate, Debug, PartialEq)]
+enum Lookup {
+    #[good]
+    Found(u32),
+    #[bad]
+    Missing,
+}
+
+#[propagate::function]
+fn total(a: Lookup, b: Option<u32>) -> Option<u32> {
+    a? + b?
+}
+
+#[test]
+fn test_mixed_enums() {
+    assert_eq!(total(Lookup::Found(1), Some(2)), Some(3));
+    assert_eq!(total(Lookup::Missing, Some(2)), None);
+    assert_eq!(total(Lookup::Found(1), None), None);
+}
+
+#[propagate::function]
+fn parse_all(strings: &[&str]) -> Result<Vec<i32>, std::num::ParseIntError> {
+    let mut ints = Vec::new();
+    for s in strings {
+        ints.push(s.parse::<i32>()?);
+    }
+    ints
+}
+
+#[test]
+fn test_loop_and_tail() {
+    assert_eq!(parse_all(&["1", "2"]), Ok(vec![1, 2]));
+    assert!(parse_all(&["1", "x"]).is_err());
+}
+
+#[propagate::function]
+fn to_lookup(flow: ControlFlow<(), u32>) -> Lookup {
+    let value = flow?;
+    assert_eq!(value, Some(value)?);
+    value * 2
+}
+
+#[test]
+fn test_derived_return_type() {
+    assert_eq!(to_lookup(ControlFlow::Continue(2)), Lookup::Found(4));
+    assert_eq!(to_lookup(ControlFlow::Break(())), Lookup::Missing);
+}
+
+#[propagate::function]
+fn closures_keep_question_mark(strings: &[&str]) -> Option<usize> {
+    // Native `?` converts through `From`
+    let double = |s: &str| -> Result<i32, Box<dyn std::error::Error>> { Ok(s.parse::<i32>()? * 2) };
+    let parsed: Result<Vec<i32>, _> = strings.iter().map(|s| double(s)).collect();
+    parsed.ok()?.len()
+}
+
+#[test]
+fn test_closures_are_left_alone() {
+    assert_eq!(closures_keep_question_mark(&["1", "2"]), Some(2));
+    assert_eq!(closures_keep_question_mark(&["1", "x"]), None);
+}
+
+macro_rules! double {
+    ($e:expr) => {
+        $e * 2
+    };
+}
+
+#[propagate::function]
+fn custom_macros_keep_question_mark(s: &str) -> Result<i32, Box<dyn std::error::Error>> {
+    // Only the arguments of standard macros are rewritten, this `?` converts through `From`
+    double!(s.parse::<i32>()?)
+}
+
+#[test]
+fn test_custom_macros_are_left_alone() {
+    assert_eq!(custom_macros_keep_question_mark("2").unwrap(), 4);
+    assert!(custom_macros_keep_question_mark("x").is_err());
+}
+
+#[propagate::function]
+fn explicit_return(a: Option<u32>) -> Option<u32> {
+    if a? == 0 {
+        return None;
+    }
+    a? - 1
+}
+
+#[test]
+fn test_explicit_return() {
+    assert_eq!(explicit_return(Some(0)), None);
+    assert_eq!(explicit_return(Some(3)), Some(2));
+    assert_eq!(explicit_return(None), None);
+}
+
+struct Counter(u32);
+
+impl Counter {
+    #[propagate::function]
+    fn add(&mut self, value: Result<u32, &'static str>) -> Result<u32, &'static str> {
+        self.0 += value?;
+        self.0
+    }
+}
+
+#[test]
+fn test_method() {
+    let mut counter = Counter(1);
+    assert_eq!(counter.add(Ok(2)), Ok(3));
+    assert_eq!(counter.add(Err("overflow")), Err("overflow"));
+}
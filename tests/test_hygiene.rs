@@ -0,0 +1,114 @@
+//! Expansions must not rely on the prelude of the caller
+#![no_implicit_prelude]
+
+use ::propagate::{
+    attempt, bad, first_good, for_good, good, is_bad, is_good, match_good, reject, retry, take,
+    while_good, Propagate,
+};
+use ::std::string::String;
+use ::std::vec::Vec;
+use ::std::{assert, assert_eq, vec};
+
+// Shadows the prelude names expansions could refer to
+#[allow(dead_code)]
+type Result<T> = ::core::result::Result<T, ()>;
+#[allow(dead_code)]
+type Option = ();
+#[allow(dead_code)]
+struct Default;
+#[allow(dead_code)]
+struct Box;
+
+#[derive(Debug, PartialEq, Propagate)]
+#[propagate(termination)]
+enum Shadow {
+    #[good]
+    Ok(u32),
+    #[bad]
+    Err(String),
+}
+
+#[derive(Debug, PartialEq, Propagate)]
+#[propagate(exhaustive)]
+enum Reply {
+    #[good]
+    Text(String),
+    #[good]
+    Code(u32),
+    #[bad(retryable)]
+    Busy,
+    #[neutral]
+    Ping,
+}
+
+use Shadow::*;
+
+fn text(reply: Reply) -> Reply {
+    let text: String = good!(reply);
+    Reply::Text(text)
+}
+
+fn double(shadow: Shadow) -> Shadow {
+    let value = good!(shadow => ::propagate::Bad);
+    Ok(value * 2)
+}
+
+#[::propagate::function]
+fn sum(a: Shadow, b: Shadow) -> Shadow {
+    a? + b?
+}
+
+#[test]
+fn test_clauses() {
+    assert_eq!(double(Ok(2)), Ok(4));
+    assert_eq!(double(Err(String::new())), Err(String::new()));
+    assert_eq!(text(Reply::Ping), Reply::Ping);
+    assert_eq!(sum(Ok(1), Ok(2)), Ok(3));
+
+    let value: u32 = good!(Ok(1); else 0);
+    let error: String = bad!(Err(String::new()) => else |_| String::new());
+    let defaulted: u32 = good!(Err(String::new()); default);
+    assert_eq!((value, error, defaulted), (1, String::new(), 0));
+
+    let mut total = 0;
+    for shadow in [Ok(1), Err(String::new()), Ok(2)] {
+        total += good!(shadow; continue);
+        let _: u32 = take!(Ok(3), Shadow::Ok[v]; continue);
+        let _ = reject!(Reply::Code(1), Reply::Busy[]; continue);
+    }
+    assert_eq!(total, 3);
+    assert!(is_good!(Ok(1)) && is_bad!(Err(String::new())));
+}
+
+#[test]
+fn test_macros() {
+    let attempted: Shadow = attempt! { good!(Ok(1)) + 1 };
+    assert_eq!(attempted, Ok(2));
+
+    let mut seen = Vec::new();
+    for_good!(value in [Ok(1), Err(String::new()), Ok(2)] {
+        seen.push(value);
+    });
+    assert_eq!(seen, [1, 2]);
+
+    let mut next = vec![Err(String::new()), Ok(1)];
+    let error: String = while_good!(let value = next.pop().unwrap() { let _: u32 = value; });
+    assert_eq!(error, String::new());
+
+    let first: u32 = first_good!(Err(String::new()), Ok(4); else 0);
+    assert_eq!(first, 4);
+
+    let retried: u32 = retry!(Reply::Code(5); max = 2; else 0);
+    assert_eq!(retried, 5);
+
+    let matched = match_good!(Reply::Busy {
+        good(code: u32) => code,
+        rest(_) => 0,
+    });
+    assert_eq!(matched, 0);
+    let matched = match_good!(Ok(6) {
+        good(value) => value,
+        bad(_) => 0,
+    });
+    assert_eq!(matched, 6);
+}
@@ -0,0 +1,135 @@
+use propagate::{bad, for_good, good, take, Propagate};
+
+#[derive(Debug, PartialEq, Propagate)]
+enum Fetch {
+    #[good]
+    Body(String),
+    #[bad]
+    Status(u16),
+    Redirect(&'static str),
+}
+
+#[test]
+fn test_returns_the_original_enum() {
+    let mut log = Vec::new();
+    let mut upper = |res: Result<String, u16>| -> Result<String, u16> {
+        let body = good!(res => inspect |status: &u16| log.push(*status));
+        Ok(body.to_uppercase())
+    };
+    assert_eq!(upper(Ok("ok".to_owned())), Ok("OK".to_owned()));
+    assert_eq!(upper(Err(404)), Err(404));
+    assert_eq!(log, [404]);
+
+    // A different return type needs a clause, like plain `good!(x)` would
+    let mut len = |res: Result<String, u16>| -> Result<usize, u16> {
+        let body = good!(res => inspect |status: &u16| log.push(*status); return Err(0));
+        Ok(body.len())
+    };
+    assert_eq!(len(Ok("four".to_owned())), Ok(4));
+    assert_eq!(len(Err(500)), Err(0));
+    assert_eq!(log, [404, 500]);
+
+    let mut lookups = 0;
+    let mut found = |lookup: Option<&'static str>| -> Option<&'static str> {
+        let found = good!(lookup => inspect |()| lookups += 1);
+        Some(found.trim())
+    };
+    assert_eq!(found(Some(" a ")), Some("a"));
+    assert_eq!(found(None), None);
+    assert_eq!(lookups, 1);
+
+    let mut seen = None;
+    let mut message = |res: Result<u16, String>| -> Result<u16, String> {
+        let message = bad!(res => inspect |code: &u16| seen = Some(*code));
+        Err(message.to_uppercase())
+    };
+    assert_eq!(message(Ok(1)), Ok(1));
+    assert_eq!(message(Err("no".to_owned())), Err("NO".to_owned()));
+    assert_eq!(seen, Some(1));
+}
+
+#[test]
+fn test_reference_scrutinee() {
+    fn parse(res: &Result<u32, String>) -> &Result<u32, String> {
+        let value: &u32 = good!(res => inspect |e| eprintln!("{e}"));
+        assert_eq!(*value, 1);
+        res
+    }
+    assert_eq!(parse(&Ok(1)), &Ok(1));
+    assert_eq!(parse(&Err("no".to_owned())), &Err("no".to_owned()));
+
+    fn bump(res: &mut Result<u32, String>) -> Option<u32> {
+        let value: &mut u32 = good!(res => inspect |e: &String| assert_eq!(e, "no"); return None);
+        *value += 1;
+        Some(*value)
+    }
+    let mut res = Ok(1);
+    assert_eq!(bump(&mut res), Some(2));
+    assert_eq!(res, Ok(2));
+    assert_eq!(bump(&mut Err("no".to_owned())), None);
+
+    let mut seen = None;
+    let mut message = |res: &mut Result<u16, String>| -> Option<usize> {
+        let message = bad!(res => inspect |code: &u16| seen = Some(*code); return None);
+        Some(message.len())
+    };
+    assert_eq!(message(&mut Err("four".to_owned())), Some(4));
+    assert_eq!(message(&mut Ok(7)), None);
+    assert_eq!(seen, Some(7));
+}
+
+#[test]
+fn test_full() {
+    let mut log = Vec::new();
+    let mut body = |fetch: Fetch| -> Fetch {
+        let body: String =
+            good!(fetch => full inspect |fetch: &Fetch| log.push(format!("{fetch:?}")));
+        Fetch::Body(body.to_uppercase())
+    };
+    assert_eq!(
+        body(Fetch::Body("ok".to_owned())),
+        Fetch::Body("OK".to_owned())
+    );
+    assert_eq!(body(Fetch::Status(500)), Fetch::Status(500));
+    assert_eq!(body(Fetch::Redirect("/")), Fetch::Redirect("/"));
+    assert_eq!(log, ["Status(500)", "Redirect(\"/\")"]);
+
+    fn reference(fetch: &Fetch) -> &Fetch {
+        let _: &String = good!(fetch => full inspect |_: &&Fetch| ());
+        fetch
+    }
+    assert_eq!(reference(&Fetch::Status(1)), &Fetch::Status(1));
+}
+
+#[test]
+fn test_then_clause() {
+    let mut failed = Vec::new();
+    let mut sum = 0;
+    for s in ["1", "x", "2", "y"] {
+        sum += good!(s.parse::<i32>() => inspect |_| failed.push(s); continue);
+    }
+    assert_eq!((sum, failed), (3, vec!["x", "y"]));
+
+    let mut inspected = 0;
+    let first_error = loop {
+        let _: i32 = good!("z".parse::<i32>() => inspect |_| inspected += 1; break 7);
+    };
+    assert_eq!((first_error, inspected), (7, 1));
+
+    let mut redirects = Vec::new();
+    for fetch in [Fetch::Redirect("/a"), Fetch::Status(1)] {
+        let to = take!(fetch, Fetch::Redirect[to] => inspect |other: &Fetch| redirects.push(format!("{other:?}")); continue);
+        redirects.push(to.to_owned());
+    }
+    assert_eq!(redirects, ["/a", "Status(1)"]);
+}
+
+#[test]
+fn test_for_good_continues() {
+    let mut errors = Vec::new();
+    let mut values = Vec::new();
+    for_good!(v in [Ok(1), Err("e"), Ok(2)] => inspect |e: &&'static str| errors.push(*e) {
+        values.push(v);
+    });
+    assert_eq!((values, errors), (vec![1, 2], vec!["e"]));
+}
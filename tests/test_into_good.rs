@@ -1,5 +1,5 @@
 use core::ops::ControlFlow::{self, Continue};
-use propagate::{good, IntoGood, Propagate};
+use propagate::{IntoGood, Propagate};
 
 #[derive(Debug, PartialEq, Propagate)]
 #[allow(dead_code)]
@@ -0,0 +1,124 @@
+#![cfg(feature = "testing")]
+
+use propagate::testing::assert_returns;
+use propagate::{for_good, while_good, Propagate};
+
+#[test]
+fn test_for_good_skips_bad_items() {
+    let mut sum = 0;
+    for_good!(num in [Some(1), None, Some(2)] {
+        sum += num;
+    });
+    assert_eq!(sum, 3);
+}
+
+#[test]
+fn test_for_good_do_then_continue() {
+    let mut errors = Vec::new();
+    let mut seen = Vec::new();
+    for_good!(num in [Ok(1), Err("a"), Ok(2), Err("b")] => do |e| errors.push(e); {
+        seen.push(num);
+    });
+    assert_eq!(seen, [1, 2]);
+    assert_eq!(errors, ["a", "b"]);
+}
+
+#[test]
+fn test_for_good_break_with_value() {
+    let items = [Ok(1), Err("first"), Err("second")];
+    let mut seen = Vec::new();
+    let res = for_good!(num in items => break |e: &str| e.len() {
+        seen.push(num);
+    });
+    assert_eq!(res, Some(5));
+    assert_eq!(seen, [1]);
+
+    let res = for_good!(num in [Some(1), None]; break 7 {
+        let _ = num;
+    });
+    assert_eq!(res, Some(7));
+
+    let res = for_good!(num in [Some(1), Some(2)]; break 7 {
+        let _ = num;
+    });
+    assert_eq!(res, None);
+}
+
+#[test]
+fn test_for_good_break_without_value() {
+    let mut seen = Vec::new();
+    for_good!(num in [Some(1), None, Some(2)]; break {
+        seen.push(num);
+    });
+    assert_eq!(seen, [1]);
+}
+
+#[test]
+fn test_for_good_labels() {
+    let mut pairs = Vec::new();
+    for_good!('outer: a in [Some(1), Some(2)] {
+        for_good!(b in [Some(10), None, Some(20)]; continue 'outer {
+            pairs.push((a, b));
+        });
+    });
+    assert_eq!(pairs, [(1, 10), (2, 10)]);
+}
+
+#[test]
+fn test_for_good_returns() {
+    assert_returns!(
+        for_good!(num in [Ok(1), Err("e")] => _ {
+            let _: i32 = num;
+        }),
+        "e"
+    );
+}
+
+#[test]
+fn test_for_good_patterns_and_borrows() {
+    let pairs = vec![Some((1, 'a')), None, Some((2, 'b'))];
+    let mut letters = String::new();
+    for_good!((_, letter) in &pairs {
+        letters.push(*letter);
+    });
+    assert_eq!(letters, "ab");
+    assert_eq!(pairs.len(), 3);
+}
+
+#[derive(Propagate, Debug, PartialEq)]
+enum Poll {
+    #[good]
+    Ready(u32),
+    #[bad]
+    Closed(&'static str),
+}
+
+#[test]
+fn test_while_good() {
+    let mut polls = vec![
+        Poll::Ready(1),
+        Poll::Ready(2),
+        Poll::Closed("eof"),
+        Poll::Ready(3),
+    ]
+    .into_iter();
+    let mut received = Vec::new();
+    let reason = while_good!(let value = polls.next().unwrap() {
+        received.push(value);
+    });
+    assert_eq!(received, [1, 2]);
+    assert_eq!(reason, "eof");
+    assert_eq!(polls.next(), Some(Poll::Ready(3)));
+}
+
+#[test]
+fn test_while_good_break() {
+    let mut count = 0;
+    let reason = while_good!('poll: let _ = Ok::<(), &str>(()) {
+        count += 1;
+        if count == 3 {
+            break 'poll "enough";
+        }
+    });
+    assert_eq!(reason, "enough");
+}
@@ -0,0 +1,136 @@
+use propagate::{match_good, Propagate};
+
+#[derive(Propagate, Debug, PartialEq)]
+enum Message {
+    #[good]
+    Text(String),
+    #[good]
+    Code(u32),
+    #[bad]
+    Error(String),
+    Ping,
+}
+
+#[derive(Propagate, Debug, PartialEq)]
+enum Switch {
+    #[good]
+    On(u8),
+    #[bad]
+    Off(u8),
+}
+
+fn describe(msg: Message) -> String {
+    match_good!(msg {
+        good(text: String) => text,
+        good(code: u32) => format!("code {code}"),
+        bad(error) => format!("error: {error}"),
+        rest(other) => format!("{other:?}"),
+    })
+}
+
+#[test]
+fn test_overloaded_payloads() {
+    assert_eq!(describe(Message::Text("hi".to_owned())), "hi");
+    assert_eq!(describe(Message::Code(7)), "code 7");
+    assert_eq!(describe(Message::Error("e".to_owned())), "error: e");
+    assert_eq!(describe(Message::Ping), "Ping");
+}
+
+#[test]
+fn test_arm_order() {
+    let first = |msg: Message| -> &'static str {
+        match_good!(msg {
+            bad(_error: String) => "bad",
+            good(_code: u32) => "code",
+            rest(_) => "rest",
+        })
+    };
+    assert_eq!(first(Message::Error("e".to_owned())), "bad");
+    assert_eq!(first(Message::Code(1)), "code");
+    // No arm takes a text, it falls through to `rest`
+    assert_eq!(first(Message::Text("t".to_owned())), "rest");
+}
+
+#[test]
+fn test_reference_with_rest() {
+    let len = |msg: &Message| -> usize {
+        match_good!(msg {
+            good(text: &String) => text.len(),
+            bad(_) => 0,
+            rest(_) => 1,
+        })
+    };
+    assert_eq!(len(&Message::Text("hi".to_owned())), 2);
+    assert_eq!(len(&Message::Error("e".to_owned())), 0);
+    assert_eq!(len(&Message::Ping), 1);
+}
+
+#[test]
+fn test_two_states() {
+    let level = |switch: Switch| -> i32 {
+        match_good!(switch {
+            bad(level) => -i32::from(level),
+            good(level) => i32::from(level),
+        })
+    };
+    assert_eq!(level(Switch::On(3)), 3);
+    assert_eq!(level(Switch::Off(3)), -3);
+
+    let parsed: Result<i32, _> = "12".parse::<i32>();
+    let n = match_good!(parsed {
+        good(n) => n,
+        bad(_) => -1,
+    });
+    assert_eq!(n, 12);
+}
+
+#[test]
+fn test_two_states_with_rest() {
+    let parse = |s: &str| -> i32 {
+        match_good!(s.parse::<i32>() {
+            good(n) => n,
+            rest(_) => -1,
+        })
+    };
+    assert_eq!(parse("5"), 5);
+    assert_eq!(parse("x"), -1);
+
+    let level = |switch: Switch| -> u8 {
+        match_good!(switch {
+            bad(level) => level * 10,
+            rest(switch) => match switch {
+                Switch::On(level) => level,
+                Switch::Off(_) => unreachable!(),
+            },
+        })
+    };
+    assert_eq!(level(Switch::Off(2)), 20);
+    assert_eq!(level(Switch::On(2)), 2);
+}
+
+#[test]
+fn test_option() {
+    let value = Some(String::from("kept"));
+    let len = match_good!(value.as_ref() {
+        good(s: &String) => s.len(),
+        bad(_) => 0,
+    });
+    assert_eq!(len, 4);
+    assert_eq!(value.as_deref(), Some("kept"));
+}
+
+#[test]
+fn test_block_bodies() {
+    let mut seen = Vec::new();
+    for msg in [Message::Code(1), Message::Ping] {
+        match_good!(msg {
+            good(code: u32) => {
+                seen.push(code);
+            }
+            rest(_) => {
+                seen.push(0);
+            }
+        })
+    }
+    assert_eq!(seen, [1, 0]);
+}
@@ -0,0 +1,66 @@
+use propagate::{good, is_good, FromBad, FromGood, Propagate};
+
+#[derive(Debug, PartialEq, Propagate)]
+#[propagate(only(good, bad, borrow, two_states))]
+enum Guarded {
+    #[good]
+    Value(u32),
+    #[bad]
+    Error(String),
+}
+
+// Hand-written, the derive would conflict without `only`
+impl FromGood<u32> for Guarded {
+    fn from_good(value: u32) -> Self {
+        Guarded::Value(value.min(100))
+    }
+}
+
+impl FromBad<String> for Guarded {
+    fn from_bad(error: String) -> Self {
+        Guarded::Error(error.to_uppercase())
+    }
+}
+
+#[test]
+fn test_picked_parts() {
+    let double = |guarded: &Guarded| -> Option<u32> {
+        let value: &u32 = good!(guarded; None);
+        Some(value * 2)
+    };
+    assert_eq!(double(&Guarded::Value(2)), Some(4));
+    assert_eq!(double(&Guarded::Error("no".to_owned())), None);
+
+    let len = |guarded: Guarded| -> usize { good!(guarded => |error: String| error.len()) as usize };
+    assert_eq!(len(Guarded::Value(3)), 3);
+    assert_eq!(len(Guarded::Error("four".to_owned())), 4);
+}
+
+#[test]
+fn test_hand_written_constructors() {
+    let forward = |result: Result<u32, String>| -> Guarded {
+        let value = good!(result => Guarded::from_bad);
+        Guarded::from_good(value + 1)
+    };
+    assert_eq!(forward(Ok(1)), Guarded::Value(2));
+    assert_eq!(forward(Ok(200)), Guarded::Value(100));
+    assert_eq!(forward(Err("bad".to_owned())), Guarded::Error("BAD".to_owned()));
+}
+
+#[derive(Propagate)]
+#[propagate(only(good, index))]
+#[allow(dead_code)]
+enum Reading {
+    #[good]
+    Celsius(f32),
+    #[bad]
+    Unplugged,
+}
+
+#[test]
+fn test_index_only() {
+    assert!(is_good!(Reading::Celsius(20.0)));
+    assert!(!is_good!(Reading::Unplugged));
+    let celsius = |reading: Reading| -> f32 { good!(reading; f32::NAN) };
+    assert_eq!(celsius(Reading::Celsius(20.0)), 20.0);
+}
@@ -0,0 +1,5 @@
+#[test]
+fn pass_compile() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/pass_compile/*.rs");
+}
@@ -0,0 +1,89 @@
+// Parenthesized casts must not trigger `unused_parens` in the caller
+#![deny(warnings)]
+
+use propagate::{bad, good, Propagate};
+
+#[derive(Debug, PartialEq, Propagate)]
+enum Log {
+    #[good]
+    SuccessMsg(String),
+    #[good]
+    SuccessCode(u32),
+    #[bad]
+    ErrorMsg(String),
+    #[bad]
+    ErrorCode(i32),
+}
+
+fn describe(code: u32) -> String {
+    format!("code {code}")
+}
+
+#[test]
+fn test_without_binding() {
+    let success_code = |log: Log| -> Option<String> { Some(describe(good!(log as (u32); None))) };
+    assert_eq!(success_code(Log::SuccessCode(7)), Some("code 7".to_owned()));
+    assert_eq!(success_code(Log::SuccessMsg("done".to_owned())), None);
+    assert_eq!(success_code(Log::ErrorCode(1)), None);
+}
+
+#[test]
+fn test_references() {
+    let mut log = Log::ErrorCode(3);
+    let reset = |log: &mut Log| {
+        *bad!(log as &mut i32;) = 0;
+    };
+    reset(&mut log);
+    assert_eq!(log, Log::ErrorCode(0));
+    let message_len = |log: &Log| -> usize { bad!(log as &String; 0).len() };
+    assert_eq!(message_len(&Log::ErrorMsg("four".to_owned())), 4);
+    assert_eq!(message_len(&log), 0);
+}
+
+#[test]
+fn test_clauses() {
+    let mut codes = Vec::new();
+    for log in [Log::SuccessCode(1), Log::ErrorCode(2), Log::SuccessCode(3)] {
+        codes.push(good!(log as (u32); continue));
+    }
+    assert_eq!(codes, [1, 3]);
+
+    let whole = |log: Log| -> Log {
+        let msg = good!(log as String);
+        Log::SuccessCode(msg.len() as u32)
+    };
+    assert_eq!(whole(Log::SuccessMsg("ok".to_owned())), Log::SuccessCode(2));
+    assert_eq!(whole(Log::SuccessCode(5)), Log::SuccessCode(5));
+
+    let full = |log: &Log| -> i32 { bad!(log as &i32 => full else |_| &-1).abs() };
+    assert_eq!(full(&Log::ErrorCode(-4)), 4);
+    assert_eq!(full(&Log::ErrorMsg(String::new())), 1);
+}
+
+#[test]
+fn test_two_states() {
+    let parse = |s: &str| -> u8 { good!(s.parse() as (u8) => else |_| 0) };
+    assert_eq!(parse("12"), 12);
+    assert_eq!(parse("x"), 0);
+    let error: Result<(), &str> = Err("failed");
+    let len = || -> usize { bad!(error as &str => else |_| "").len() };
+    assert_eq!(len(), 6);
+}
+
+#[test]
+fn test_parenthesized_cast() {
+    // `(x as T)` casts the scrutinee instead of naming the payload type
+    let first = |bytes: *const u8| -> Option<u16> {
+        let words = good!((bytes as *const u16); None);
+        Some(unsafe { words.as_ptr().read_unaligned() })
+    };
+    assert_eq!(first([1u8, 0].as_ptr()), Some(u16::from_ne_bytes([1, 0])));
+    assert_eq!(first(std::ptr::null()), None);
+    let level = |level: u8| -> Option<u32> { Some(good!((level as u32).checked_sub(1); None)) };
+    assert_eq!(level(2), Some(1));
+    assert_eq!(level(0), None);
+    // The parentheses are not forwarded to the expansion
+    let head = |values: &[u32]| -> Option<u32> { Some(*good!((values.first()); None)) };
+    assert_eq!(head(&[1, 2]), Some(1));
+    assert_eq!(head(&[]), None);
+}
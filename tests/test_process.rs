@@ -0,0 +1,73 @@
+#![cfg(all(unix, feature = "std"))]
+
+use propagate::{bad, good, Bad, ExitFailure, FromBad};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, ExitCode, ExitStatus, Output};
+
+fn sh(script: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.args(["-c", script]);
+    command
+}
+
+fn status(script: &str) -> ExitStatus {
+    sh(script).status().unwrap()
+}
+
+fn output(script: &str) -> Output {
+    sh(script).output().unwrap()
+}
+
+#[test]
+fn test_exit_status() {
+    let failure = |status: ExitStatus| -> Option<ExitFailure> {
+        good!(status => Some);
+        None
+    };
+    assert_eq!(failure(status("exit 0")), None);
+    assert_eq!(failure(status("exit 3")), Some(ExitFailure::Code(3)));
+    assert_eq!(failure(status("kill -9 $$")), Some(ExitFailure::Signal(9)));
+    // Stopped by `SIGSTOP`, as `waitpid` with `WUNTRACED` reports it, has neither
+    let stopped = ExitStatus::from_raw(0x137f);
+    assert_eq!(failure(stopped), Some(ExitFailure::Other));
+    assert_eq!(ExitCode::from(ExitFailure::Other), ExitCode::FAILURE);
+
+    let code = |status: ExitStatus| -> i32 {
+        let ExitFailure::Code(code) = bad!(status; -1) else {
+            return -2;
+        };
+        code
+    };
+    assert_eq!(code(status("exit 0")), -1);
+    assert_eq!(code(status("exit 7")), 7);
+}
+
+#[test]
+fn test_output_stdout() {
+    let stdout = |output: Output| -> Vec<u8> { good!(output; Vec::new()) };
+    assert_eq!(stdout(output("echo hi")), b"hi\n");
+    assert_eq!(stdout(output("echo hi; exit 1")), b"");
+
+    // By reference, the stderr of a failed process is still there
+    let failed = output("echo oops >&2; exit 2");
+    let stdout_ref = |output| -> Result<&[u8], ExitFailure> { Ok(good!(output => Bad)) };
+    assert_eq!(stdout_ref(&failed), Err(ExitFailure::Code(2)));
+    assert_eq!(failed.stderr, b"oops\n");
+}
+
+#[test]
+fn test_exit_code() {
+    let run = |script: &str| -> ExitCode {
+        good!(status(script) => Bad);
+        ExitCode::SUCCESS
+    };
+    assert_eq!(run("exit 0"), ExitCode::SUCCESS);
+    assert_eq!(run("exit 4"), ExitCode::from(4));
+    assert_eq!(run("kill -15 $$"), ExitCode::from(128 + 15));
+    assert_eq!(ExitCode::from_bad(5u8), ExitCode::from(5));
+    assert_eq!(
+        ExitCode::from(ExitFailure::Code(256)),
+        ExitCode::from(1),
+        "a code that reads as success must still fail"
+    );
+}
@@ -0,0 +1,81 @@
+use core::ptr::{self, NonNull};
+use propagate::{good, is_bad, is_good, Bad, Good};
+
+fn ffi_lookup(found: bool, value: &mut u32) -> *mut u32 {
+    match found {
+        true => value,
+        false => ptr::null_mut(),
+    }
+}
+
+#[test]
+fn test_mut_pointer() {
+    let mut value = 3;
+    let increment = |p: *mut u32| -> Result<u32, &'static str> {
+        let p: NonNull<u32> = good!(p; return Err("null"));
+        unsafe {
+            *p.as_ptr() += 1;
+            Ok(*p.as_ptr())
+        }
+    };
+    assert_eq!(increment(ffi_lookup(true, &mut value)), Ok(4));
+    assert_eq!(increment(ffi_lookup(false, &mut value)), Err("null"));
+    assert_eq!(value, 4);
+}
+
+#[test]
+fn test_const_pointer() {
+    let value = 5;
+    let read =
+        |p: *const u32| -> u32 { unsafe { *good!(p => else |_| NonNull::from(&0)).as_ref() } };
+    assert_eq!(read(&value), 5);
+    assert_eq!(read(ptr::null()), 0);
+    assert!(is_good!(&value as *const u32));
+    assert!(is_bad!(ptr::null::<u32>()));
+}
+
+#[test]
+fn test_propagate_null() {
+    let forward = |p: *const u8| -> *const u8 {
+        let p = good!(p);
+        unsafe { p.as_ptr().add(1) }
+    };
+    let bytes = [1u8, 2];
+    assert_eq!(forward(bytes.as_ptr()), unsafe { bytes.as_ptr().add(1) });
+    assert!(forward(ptr::null()).is_null());
+
+    let null: *mut u8 = Bad(());
+    assert!(null.is_null());
+    let mut byte = 0u8;
+    let p: *mut u8 = Good(NonNull::from(&mut byte));
+    assert_eq!(p, &mut byte as *mut u8);
+}
+
+#[test]
+fn test_option_non_null() {
+    let mut value = 1u32;
+    let get =
+        |p: Option<NonNull<u32>>| -> Option<u32> { Some(unsafe { *good!(p; None).as_ptr() }) };
+    assert_eq!(get(NonNull::new(&mut value)), Some(1));
+    assert_eq!(get(None), None);
+}
+
+#[test]
+fn test_unsized_pointee() {
+    let len = |p: *const str| -> Option<usize> { Some(unsafe { good!(p; None).as_ref() }.len()) };
+    assert_eq!(len("four"), Some(4));
+    let null = ptr::null::<[u8; 0]>() as *const [u8];
+    assert!(is_bad!(null));
+
+    let mut bytes = [1u8, 2];
+    let clear = |p: *mut [u8]| -> Result<(), ()> {
+        let mut p = good!(p => Bad);
+        unsafe { p.as_mut().fill(0) };
+        Ok(())
+    };
+    assert_eq!(clear(&mut bytes[..]), Ok(()));
+    assert_eq!(bytes, [0, 0]);
+    assert_eq!(clear(null as *mut [u8]), Err(()));
+    let p: *const str = Good(NonNull::from("ok"));
+    assert_eq!(unsafe { &*p }, "ok");
+}
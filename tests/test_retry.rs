@@ -0,0 +1,105 @@
+#![cfg(feature = "testing")]
+
+use propagate::testing::{assert_continues, assert_returns, assert_yields};
+use propagate::{retry, Propagate, Retryable};
+use std::io::{Error, ErrorKind};
+
+#[derive(Propagate, Debug, PartialEq)]
+enum Read {
+    #[good]
+    Byte(u8),
+    #[bad(retryable)]
+    WouldBlock,
+    #[bad(retryable)]
+    Interrupted(u32),
+    #[bad]
+    Closed,
+}
+
+fn polls(reads: Vec<Read>) -> impl FnMut() -> Read {
+    let mut reads = reads.into_iter();
+    move || reads.next().expect("polled too many times")
+}
+
+#[test]
+fn test_derived_retryable() {
+    assert!(Read::WouldBlock.is_retryable());
+    assert!(Read::Interrupted(1).is_retryable());
+    assert!(!Read::Closed.is_retryable());
+    assert!(!Read::Byte(0).is_retryable());
+}
+
+#[test]
+fn test_retries_until_not_retryable() {
+    let mut poll = polls(vec![Read::WouldBlock, Read::Interrupted(4), Read::Byte(7)]);
+    assert_yields!(retry!(poll()), 7);
+
+    let mut poll = polls(vec![Read::WouldBlock, Read::Closed]);
+    assert_returns!(retry!(poll()), Read::Closed);
+}
+
+#[test]
+fn test_max_attempts() {
+    let mut poll = polls(vec![Read::WouldBlock, Read::WouldBlock, Read::Byte(1)]);
+    assert_returns!(retry!(poll(); max = 2), Read::WouldBlock);
+
+    let mut poll = polls(vec![Read::WouldBlock, Read::WouldBlock, Read::Byte(1)]);
+    assert_yields!(retry!(poll(); max = 3), 1);
+
+    // The expression is always evaluated once
+    let max = 0;
+    let mut poll = polls(vec![Read::WouldBlock]);
+    assert_returns!(retry!(poll(); max = max), Read::WouldBlock);
+}
+
+#[test]
+fn test_backoff() {
+    let mut waited = Vec::new();
+    let mut poll = polls(vec![Read::WouldBlock, Read::WouldBlock, Read::Byte(1)]);
+    let byte = retry!(poll(); backoff = |attempt| waited.push(attempt * 10); else 0);
+    assert_eq!(byte, 1);
+    assert_eq!(waited, [10, 20]);
+}
+
+#[test]
+fn test_when_predicate() {
+    let mut inputs = ["x", "y", "3"].into_iter();
+    let mut parse = || inputs.next().unwrap().parse::<i32>();
+    assert_yields!(retry!(parse(); when = |res| res.is_err(), max = 5), 3);
+
+    let mut calls = 0;
+    let value = retry!({ calls += 1; Some(calls) }; when = |v| *v < Some(3); else 0);
+    assert_eq!(value, 3);
+}
+
+#[test]
+fn test_clauses() {
+    let mut poll = polls(vec![Read::WouldBlock, Read::Closed]);
+    assert_continues!(retry!(poll(); continue));
+
+    let mut poll = polls(vec![Read::Closed]);
+    assert_yields!(retry!(poll(); max = 1; else 9), 9);
+
+    let mut poll = polls(vec![Read::Closed]);
+    let closed = |read: Read| u8::from(read == Read::Closed);
+    assert_yields!(retry!(poll() => full else closed), 1);
+}
+
+#[test]
+fn test_io_errors() {
+    assert!(ErrorKind::Interrupted.is_retryable());
+    assert!(ErrorKind::WouldBlock.is_retryable());
+    assert!(!ErrorKind::NotFound.is_retryable());
+
+    let mut errors = vec![
+        Err(Error::from(ErrorKind::Interrupted)),
+        Err(Error::from(ErrorKind::WouldBlock)),
+        Ok(5),
+    ]
+    .into_iter();
+    assert_yields!(retry!(errors.next().unwrap()), 5);
+
+    let mut errors = vec![Err(Error::from(ErrorKind::NotFound)), Ok(5)].into_iter();
+    let kind = retry!(errors.next().unwrap() => else |e: Error| e.kind() as i32);
+    assert_eq!(kind, ErrorKind::NotFound as i32);
+}
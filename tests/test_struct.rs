@@ -0,0 +1,84 @@
+use propagate::{bad, good, is_bad, is_good, Propagate};
+
+#[derive(Debug, PartialEq, Propagate)]
+#[propagate(good_if = Self::is_success)]
+struct Status(u16);
+
+impl Status {
+    fn is_success(&self) -> bool {
+        (200..300).contains(&self.0)
+    }
+}
+
+#[derive(Debug, PartialEq, Propagate)]
+#[propagate(good_if = |reply: &Reply| reply.code == 0, good = body, bad = code)]
+struct Reply {
+    code: i32,
+    body: Vec<u8>,
+}
+
+#[derive(Propagate)]
+#[propagate(good_if = |pair: &Pair<T>| pair.0 <= pair.1, good = 1, bad = 0)]
+struct Pair<T: PartialOrd>(T, T);
+
+#[test]
+fn test_whole_struct() {
+    let check = |status: Status| -> Result<u16, Status> {
+        let status = good!(status => Err);
+        Ok(status.0)
+    };
+    assert_eq!(check(Status(204)), Ok(204));
+    assert_eq!(check(Status(404)), Err(Status(404)));
+    assert!(is_good!(Status(200)));
+    assert!(is_bad!(Status(500)));
+}
+
+#[test]
+fn test_projections() {
+    let body_len = |reply: &Reply| -> usize { good!(reply; 0).len() };
+    let ok = Reply {
+        code: 0,
+        body: vec![1, 2, 3],
+    };
+    let failed = Reply {
+        code: 2,
+        body: Vec::new(),
+    };
+    assert_eq!(body_len(&ok), 3);
+    assert_eq!(body_len(&failed), 0);
+    assert_eq!(bad!(failed => else |_| 0), 2);
+    assert!(is_good!(ok));
+
+    let mut reply = Reply {
+        code: 0,
+        body: Vec::new(),
+    };
+    let push = |reply: &mut Reply| {
+        good!(reply;).push(7);
+    };
+    push(&mut reply);
+    assert_eq!(reply.body, [7]);
+    reply.code = 1;
+    push(&mut reply);
+    assert_eq!(reply.body, [7]);
+
+    let forward = |reply: Reply| -> Reply {
+        let body = good!(reply);
+        Reply { code: 0, body }
+    };
+    assert_eq!(
+        forward(Reply {
+            code: 3,
+            body: vec![1]
+        })
+        .code,
+        3
+    );
+}
+
+#[test]
+fn test_generic_tuple_struct() {
+    let upper = |pair: Pair<f32>| -> f32 { good!(pair => |lower: f32| -lower) };
+    assert_eq!(upper(Pair(1.0, 2.0)), 2.0);
+    assert_eq!(upper(Pair(3.0, 2.0)), -3.0);
+}
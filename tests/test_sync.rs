@@ -0,0 +1,115 @@
+#![cfg(feature = "std")]
+
+use propagate::{bad, good, is_bad, is_good, is_neutral, retry};
+use std::sync::mpsc::{self, RecvTimeoutError, TryRecvError, TrySendError};
+use std::sync::{Mutex, RwLock, TryLockError};
+use std::thread;
+use std::time::Duration;
+
+fn poisoned<T: Send>(lock: &Mutex<T>) {
+    thread::scope(|s| {
+        s.spawn(|| {
+            let _guard = lock.lock().unwrap();
+            panic!("poisoning the lock");
+        })
+        .join()
+        .unwrap_err();
+    });
+}
+
+#[test]
+fn test_recover_poisoned_lock() {
+    let counter = Mutex::new(1);
+    poisoned(&counter);
+    assert!(counter.is_poisoned());
+    let mut count = good!(counter.lock(); recover);
+    *count += 1;
+    drop(count);
+    assert_eq!(*good!(counter.lock(); recover), 2);
+
+    let lock = RwLock::new(vec![1]);
+    good!(lock.write(); recover).push(2);
+    assert_eq!(*good!(lock.read(); recover), [1, 2]);
+}
+
+#[test]
+fn test_recover_is_a_keyword_only_alone() {
+    let recover = |_: &str| 0;
+    let parse = |s: &str| -> i32 { good!(s.parse::<i32>(); recover(s)) };
+    assert_eq!(parse("3"), 3);
+    assert_eq!(parse("x"), 0);
+}
+
+#[test]
+#[allow(unused_variables)]
+fn test_recover_is_a_keyword_next_to_a_local() {
+    let counter = Mutex::new(1);
+    poisoned(&counter);
+    let recover = Mutex::new(0);
+    assert_eq!(*good!(counter.lock(); recover), 1);
+
+    fn returned(counter: &Mutex<u32>) -> u32 {
+        let recover = 0;
+        *good!(counter.lock(); (recover))
+    }
+    assert_eq!(returned(&counter), 0);
+}
+
+#[test]
+fn test_try_lock() {
+    let lock = Mutex::new(0);
+    let guard = lock.lock().unwrap();
+    let error = lock.try_lock().unwrap_err();
+    assert!(is_neutral!(&error) && !is_bad!(&error) && !is_good!(&error));
+    assert!(matches!(error, TryLockError::WouldBlock));
+    drop(guard);
+
+    poisoned(&lock);
+    let error = lock.try_lock().unwrap_err();
+    assert!(is_bad!(&error) && !is_neutral!(&error));
+    let guard = bad!(error; unreachable!()).into_inner();
+    assert_eq!(*guard, 0);
+}
+
+#[test]
+fn test_channels() {
+    let disconnected = |error: TryRecvError| -> bool {
+        bad!(error; false);
+        true
+    };
+    let (tx, rx) = mpsc::sync_channel::<u8>(1);
+    let error = rx.try_recv().unwrap_err();
+    assert!(is_neutral!(&error));
+    assert!(!disconnected(error));
+
+    tx.send(1).unwrap();
+    let error = tx.try_send(2).unwrap_err();
+    assert!(is_neutral!(&error));
+    assert!(matches!(error, TrySendError::Full(2)));
+
+    // Retries while the channel is empty, gives up on the first message
+    let sender = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        tx.send(3).unwrap();
+    });
+    assert_eq!(rx.recv().unwrap(), 1);
+    let message = retry!(rx.try_recv(); backoff = |_| thread::yield_now(); unreachable!());
+    assert_eq!(message, 3);
+    sender.join().unwrap();
+
+    let error = rx.try_recv().unwrap_err();
+    assert!(is_bad!(&error));
+    assert!(disconnected(error));
+    let error = rx.recv_timeout(Duration::ZERO).unwrap_err();
+    assert_eq!(error, RecvTimeoutError::Disconnected);
+    assert!(is_bad!(&error));
+}
+
+#[test]
+fn test_send_disconnected() {
+    let (tx, rx) = mpsc::sync_channel::<String>(1);
+    drop(rx);
+    let error = tx.try_send("lost".to_owned()).unwrap_err();
+    let value: String = bad!(error; unreachable!());
+    assert_eq!(value, "lost");
+}
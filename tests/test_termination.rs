@@ -0,0 +1,74 @@
+use propagate::{good, Propagate};
+use std::fmt;
+use std::process::{ExitCode, Termination};
+
+#[derive(Debug, Propagate)]
+#[propagate(termination)]
+enum Status {
+    #[good]
+    Done,
+    #[good(exit_code = 3)]
+    NothingToDo,
+    #[bad(exit_code = 2)]
+    Usage(String),
+    #[bad]
+    Failed(u32),
+    Skipped,
+}
+
+#[derive(Propagate)]
+#[propagate(termination(display))]
+enum Step<T> {
+    #[good]
+    Ok(T),
+    #[bad(exit_code = 4)]
+    Failed(String),
+}
+
+impl<T> fmt::Display for Step<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Ok(_) => write!(f, "ok"),
+            Step::Failed(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+#[test]
+fn test_exit_codes() {
+    assert_eq!(Status::Done.report(), ExitCode::SUCCESS);
+    assert_eq!(Status::NothingToDo.report(), ExitCode::from(3));
+    assert_eq!(
+        Status::Usage("--help".to_owned()).report(),
+        ExitCode::from(2)
+    );
+    assert_eq!(Status::Failed(1).report(), ExitCode::FAILURE);
+    assert_eq!(Status::Skipped.report(), ExitCode::SUCCESS);
+}
+
+#[test]
+fn test_display_report() {
+    // `Step<T>` has no `Debug`, it is reported through `Display`
+    struct Opaque;
+    assert_eq!(Step::Ok(Opaque).report(), ExitCode::SUCCESS);
+    assert_eq!(
+        Step::<Opaque>::Failed("disk full".to_owned()).report(),
+        ExitCode::from(4)
+    );
+}
+
+#[test]
+fn test_propagate_out_of_main() {
+    fn parse_args(args: &[&str]) -> Status {
+        match args {
+            [] => Status::Usage("expected an argument".to_owned()),
+            _ => Status::Done,
+        }
+    }
+    fn main(args: &[&str]) -> Status {
+        good!(parse_args(args));
+        Status::Done
+    }
+    assert_eq!(main(&["run"]).report(), ExitCode::SUCCESS);
+    assert_eq!(main(&[]).report(), ExitCode::from(2));
+}
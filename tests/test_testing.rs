@@ -0,0 +1,71 @@
+#![cfg(feature = "testing")]
+
+use propagate::testing::{assert_breaks, assert_continues, assert_returns, assert_yields, Branch};
+use propagate::{bad, good};
+
+#[test]
+fn test_branches() {
+    assert_yields!(good!(Some(1)), 1);
+    assert_yields!(bad!(Err::<i32, &str>("e")), "e");
+    assert_returns!(good!(None::<i32>));
+    assert_returns!(good!(Err::<i32, &str>("e") => |e: &str| e.len()), 1);
+    assert_continues!(good!(None::<i32>; continue));
+    assert_breaks!(good!(None::<i32>; break));
+    assert_breaks!(
+        good!(Err::<i32, &str>("oops") => break |e: &str| e.len()),
+        4
+    );
+}
+
+#[test]
+fn test_branch_display() {
+    let branch: Branch<(), (), ()> = Branch::Continued;
+    assert_eq!(branch.to_string(), "continued");
+    let branch: Branch<(), (), ()> = Branch::Yielded(());
+    assert_eq!(branch.to_string(), "yielded a value");
+}
+
+#[test]
+#[should_panic(expected = "expected the macro to yield a value, but it returned")]
+fn test_yields_but_returns() {
+    assert_yields!(good!(None::<i32>), 1);
+}
+
+#[test]
+#[should_panic(expected = "expected the macro to continue, but it broke")]
+fn test_continues_but_breaks() {
+    assert_continues!(good!(None::<i32>; break));
+}
+
+#[test]
+#[should_panic(expected = "expected the macro to break, but it continued")]
+fn test_breaks_but_continues() {
+    assert_breaks!(good!(None::<i32>; continue));
+}
+
+#[test]
+#[should_panic(expected = "expected the macro to return, but it yielded a value")]
+fn test_returns_but_yields() {
+    assert_returns!(good!(Some(1)));
+}
+
+#[test]
+#[should_panic(expected = "the macro yielded `1`, expected `2`")]
+fn test_yields_wrong_value() {
+    assert_yields!(good!(Some(1)), 2);
+}
+
+#[test]
+#[should_panic(expected = "the macro broke with `4`, expected `5`")]
+fn test_breaks_wrong_value() {
+    assert_breaks!(
+        good!(Err::<i32, &str>("oops") => break |e: &str| e.len()),
+        5
+    );
+}
+
+#[test]
+#[should_panic(expected = "genuine panic")]
+fn test_other_panics_propagate() {
+    assert_yields!(good!(None::<i32> => expect "genuine panic"), 1);
+}